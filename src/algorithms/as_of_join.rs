@@ -0,0 +1,88 @@
+//! Joins records with the version of a dimension current at their event time.
+
+use timely::dataflow::Scope;
+
+use ::{Collection, ExchangeData, Hashable};
+use ::lattice::Lattice;
+use ::operators::*;
+use ::operators::arrange::ArrangeByKey;
+
+/// Extension trait for the `as_of_join` method.
+pub trait AsOfJoin<G: Scope, K, E, V1> {
+    /// Matches each record with the dimension row for its key that was current at its event time.
+    ///
+    /// Records in `self` are `(key, (event, val))` facts, and records in `dimension` are versions
+    /// `(key, (valid_from, row))` of the dimension, each current from its `valid_from` event time
+    /// until the next version for the same key. Each fact is matched with the version with greatest
+    /// `valid_from` less or equal to its `event`, or with none if there is no such version. If several
+    /// versions share a `valid_from`, the greatest row is used.
+    ///
+    /// The result is maintained as both inputs change. The versions of each key are first reduced to
+    /// the intervals of event time in which each is current, and facts are then joined with the
+    /// intervals of their key, so that a change to a fact is matched only against those intervals and
+    /// a change to a version only affects the facts of the intervals it changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    /// use differential_dataflow::algorithms::as_of_join::AsOfJoin;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let prices = scope.new_collection_from(vec![(0, (0, 'a')), (0, (5, 'b'))]).1;
+    ///         let orders = scope.new_collection_from(vec![(0, (3, 10)), (0, (7, 11))]).1;
+    ///
+    ///         let expected = scope.new_collection_from(vec![(0, (3, 10, 'a')), (0, (7, 11, 'b'))]).1;
+    ///
+    ///         orders.as_of_join(&prices)
+    ///               .assert_eq(&expected);
+    ///     });
+    /// }
+    /// ```
+    fn as_of_join<V2: ExchangeData>(&self, dimension: &Collection<G, (K, (E, V2))>) -> Collection<G, (K, (E, V1, V2))>;
+}
+
+impl<G, K, E, V1> AsOfJoin<G, K, E, V1> for Collection<G, (K, (E, V1))>
+where
+    G: Scope,
+    G::Timestamp: Lattice+Ord,
+    K: ExchangeData+Hashable,
+    E: ExchangeData,
+    V1: ExchangeData,
+{
+    fn as_of_join<V2: ExchangeData>(&self, dimension: &Collection<G, (K, (E, V2))>) -> Collection<G, (K, (E, V1, V2))> {
+
+        // The intervals `[valid_from, until)` in which each version is current, where `until` is
+        // absent for the latest version.
+        let intervals: Collection<G, (K, (E, Option<E>, V2))> = dimension.reduce(|_key, input, output| {
+            let mut current: Option<(&E, &V2)> = None;
+            for &(&(ref valid_from, ref row), count) in input.iter() {
+                if count > 0 {
+                    if let Some((from, prior)) = current {
+                        if from != valid_from {
+                            output.push(((from.clone(), Some(valid_from.clone()), prior.clone()), 1));
+                        }
+                    }
+                    current = Some((valid_from, row));
+                }
+            }
+            if let Some((from, row)) = current {
+                output.push(((from.clone(), None, row.clone()), 1));
+            }
+        });
+
+        self.join_core(&intervals.arrange_by_key(), |key, &(ref event, ref val), &(ref from, ref until, ref row)| {
+            if from <= event && until.as_ref().map(|until| event < until).unwrap_or(true) {
+                Some((key.clone(), (event.clone(), val.clone(), row.clone())))
+            }
+            else {
+                None
+            }
+        })
+    }
+}
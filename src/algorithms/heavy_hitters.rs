@@ -0,0 +1,173 @@
+//! Approximate most frequent values for each key, in bounded space.
+//!
+//! The implementation follows the SpaceSaving summary: each key tracks counts for at most a fixed
+//! number of values, and a new value displaces the least frequent tracked value, inheriting its
+//! count. For input consisting only of insertions, estimates are never less than the true count of
+//! a value, and values whose frequency is large relative to the total for their key are guaranteed
+//! to be tracked.
+//!
+//! Retractions reduce the estimate of a tracked value, removing it once the estimate is no longer
+//! positive, and retractions of untracked values are ignored. Because a tracked value's estimate may
+//! include counts inherited from values it displaced, retractions can remove those counts too, and
+//! the guarantees above do not hold for input containing retractions.
+
+use std::collections::BTreeMap;
+
+use timely::order::TotalOrder;
+use timely::dataflow::Scope;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::Operator;
+
+use ::{Collection, AsCollection, ExchangeData, Hashable};
+use ::lattice::Lattice;
+use ::operators::stash::Stash;
+
+/// Extension trait for the `heavy_hitters` method.
+pub trait HeavyHitters<G: Scope, K, V> {
+    /// Reports the `n` values with greatest estimated frequency for each key, and their estimates.
+    ///
+    /// Each key tracks at most `capacity` values, which must be at least `n`; larger capacities
+    /// give more accurate estimates. The output changes as membership or estimates change, with
+    /// ties in estimates broken in favor of lesser values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    /// use differential_dataflow::algorithms::heavy_hitters::HeavyHitters;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let data = vec![(0, 'a'), (0, 'b'), (0, 'a'), (0, 'c'), (0, 'a'), (0, 'b')];
+    ///         let expected = vec![(0, ('a', 3)), (0, ('b', 2))];
+    ///
+    ///         scope.new_collection_from(data).1
+    ///              .heavy_hitters(2, 3)
+    ///              .assert_eq(&scope.new_collection_from(expected).1);
+    ///     });
+    /// }
+    /// ```
+    fn heavy_hitters(&self, n: usize, capacity: usize) -> Collection<G, (K, (V, isize))>;
+}
+
+impl<G, K, V> HeavyHitters<G, K, V> for Collection<G, (K, V)>
+where
+    G: Scope,
+    G::Timestamp: Lattice+TotalOrder,
+    K: ExchangeData+Hashable,
+    V: ExchangeData,
+{
+    fn heavy_hitters(&self, n: usize, capacity: usize) -> Collection<G, (K, (V, isize))> {
+
+        assert!(n <= capacity, "heavy_hitters: capacity {} is less than n {}", capacity, n);
+
+        let exchange = Exchange::new(|update: &((K, V), G::Timestamp, isize)| (update.0).0.hashed().into());
+
+        self.inner
+            .unary_frontier(exchange, "HeavyHitters", move |_cap, _info| {
+
+                let mut vector = Vec::new();
+                let mut stash = Stash::new();
+
+                // Summaries for each key, and the values and estimates most recently reported.
+                let mut summaries = BTreeMap::<K, Summary<V>>::new();
+                let mut reported = BTreeMap::<K, Vec<(V, isize)>>::new();
+
+                move |input, output| {
+
+                    input.for_each(|cap, data| {
+                        data.swap(&mut vector);
+                        for (data, time, diff) in vector.drain(..) {
+                            stash.push(&cap, time, (data, diff));
+                        }
+                    });
+
+                    // Apply the updates at each complete time, in order of time.
+                    for (cap, mut updates) in stash.drain_complete(input.frontier().frontier()) {
+
+                        let time = cap.time().clone();
+                        updates.sort();
+
+                        let mut session = output.session(&cap);
+                        let mut index = 0;
+                        while index < updates.len() {
+
+                            let key = (updates[index].0).0.clone();
+                            let (top, empty) = {
+                                let summary = summaries.entry(key.clone()).or_insert_with(|| Summary::new(capacity));
+                                while index < updates.len() && (updates[index].0).0 == key {
+                                    summary.update(&(updates[index].0).1, updates[index].1);
+                                    index += 1;
+                                }
+                                (summary.top(n), summary.is_empty())
+                            };
+                            if empty {
+                                summaries.remove(&key);
+                            }
+
+                            let prior = reported.remove(&key).unwrap_or_else(Vec::new);
+                            for &(ref val, count) in prior.iter().filter(|x| !top.contains(x)) {
+                                session.give(((key.clone(), (val.clone(), count)), time.clone(), -1));
+                            }
+                            for &(ref val, count) in top.iter().filter(|x| !prior.contains(x)) {
+                                session.give(((key.clone(), (val.clone(), count)), time.clone(), 1));
+                            }
+                            if !top.is_empty() {
+                                reported.insert(key, top);
+                            }
+                        }
+                    }
+                }
+            })
+            .as_collection()
+    }
+}
+
+/// Estimated counts for at most `capacity` values.
+struct Summary<V> {
+    capacity: usize,
+    counts: BTreeMap<V, isize>,
+}
+
+impl<V: Ord+Clone> Summary<V> {
+
+    fn new(capacity: usize) -> Self {
+        Summary { capacity, counts: BTreeMap::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    fn update(&mut self, val: &V, diff: isize) {
+        if let Some(count) = self.counts.get(val).cloned() {
+            if count + diff > 0 {
+                self.counts.insert(val.clone(), count + diff);
+            }
+            else {
+                self.counts.remove(val);
+            }
+        }
+        else if diff > 0 {
+            if self.counts.len() < self.capacity {
+                self.counts.insert(val.clone(), diff);
+            }
+            else if let Some((least, count)) = self.counts.iter().min_by_key(|x| *x.1).map(|(v, c)| (v.clone(), *c)) {
+                // The displaced count bounds the error in the estimate for `val`.
+                self.counts.remove(&least);
+                self.counts.insert(val.clone(), count + diff);
+            }
+        }
+    }
+
+    fn top(&self, n: usize) -> Vec<(V, isize)> {
+        let mut top: Vec<(V, isize)> = self.counts.iter().map(|(v, c)| (v.clone(), *c)).collect();
+        top.sort_by(|x, y| y.1.cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+        top.truncate(n);
+        top
+    }
+}
@@ -2,4 +2,7 @@
 
 pub mod identifiers;
 pub mod prefix_sum;
+pub mod as_of_join;
+pub mod heavy_hitters;
+pub mod reconcile;
 pub mod graphs;
\ No newline at end of file
@@ -0,0 +1,69 @@
+//! Commands that bring one collection in line with another.
+
+use timely::dataflow::Scope;
+
+use ::{Collection, ExchangeData, Hashable};
+use ::lattice::Lattice;
+use ::operators::*;
+
+/// A change to make to a record of the actual collection.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Abomonation, Serialize, Deserialize)]
+pub enum Command {
+    /// The record should be inserted.
+    Insert,
+    /// The record should be deleted.
+    Delete,
+}
+
+/// Extension trait for the `reconcile` method.
+pub trait Reconcile<G: Scope, D> {
+    /// The commands that would make `actual` equal to `self`.
+    ///
+    /// The result contains `(record, Command::Insert)` with count `n` for each record whose count
+    /// in `self` exceeds its count in `actual` by `n`, and `(record, Command::Delete)` with count `n`
+    /// for each record whose count in `actual` exceeds its count in `self` by `n`. The commands are
+    /// consolidated, and are maintained as both collections change; applying the changes to the
+    /// commands to an external system keeps its pending work up to date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    /// use differential_dataflow::algorithms::reconcile::{Reconcile, Command};
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let desired = scope.new_collection_from(vec![1, 2, 3]).1;
+    ///         let actual = scope.new_collection_from(vec![2, 3, 4]).1;
+    ///
+    ///         let expected = scope.new_collection_from(vec![(1, Command::Insert), (4, Command::Delete)]).1;
+    ///
+    ///         desired.reconcile(&actual)
+    ///                .assert_eq(&expected);
+    ///     });
+    /// }
+    /// ```
+    fn reconcile(&self, actual: &Collection<G, D>) -> Collection<G, (D, Command)>;
+}
+
+impl<G, D> Reconcile<G, D> for Collection<G, D>
+where
+    G: Scope,
+    G::Timestamp: Lattice+Ord,
+    D: ExchangeData+Hashable,
+{
+    fn reconcile(&self, actual: &Collection<G, D>) -> Collection<G, (D, Command)> {
+        self.concat(&actual.negate())
+            .map(|record| (record, ()))
+            .reduce(|_record, input, output| {
+                if let Some(&(_, count)) = input.first() {
+                    if count > 0 { output.push((Command::Insert, count)); }
+                    if count < 0 { output.push((Command::Delete, -count)); }
+                }
+            })
+    }
+}
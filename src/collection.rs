@@ -640,6 +640,10 @@ impl<G: Scope, D: Data, R: Abelian> Collection<G, D, R> where G::Timestamp: Data
     /// Typically, a timely dataflow computation runs to completion on drop, and so clean exit from a program should
     /// indicate that this assertion never found cause to complain.
     ///
+    /// On failure, the panic message lists the `(record, time, diff)` updates of `self` minus `other`
+    /// that were found together, each at its own time, so that positive differences are records in
+    /// excess in `self`, and negative differences are records missing from `self`.
+    ///
     /// # Examples
     ///
     /// ```
@@ -666,9 +670,85 @@ impl<G: Scope, D: Data, R: Abelian> Collection<G, D, R> where G::Timestamp: Data
           R: ::ExchangeData+Hashable,
           G::Timestamp: Lattice+Ord
     {
-        self.negate()
-            .concat(other)
-            .assert_empty();
+        self.mismatches(other)
+            .inspect_batch(|_time, updates| {
+                let mut message = "Assertion failed: collections differ; updates of `self` minus `other`:".to_owned();
+                for (data, time, diff) in updates.iter() {
+                    message.push_str(&format!("\n    ({:?}, {:?}, {:?})", data, time, diff));
+                }
+                panic!("{}", message);
+            });
+    }
+
+    /// The consolidated updates of `self` minus `other`.
+    ///
+    /// The result is empty exactly when the two collections are equal, and is otherwise the updates
+    /// `assert_eq` would report. This allows tests to collect or report differences themselves,
+    /// rather than panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let data = scope.new_collection_from(1 .. 10).1;
+    ///         let odds = data.filter(|x| x % 2 == 1);
+    ///
+    ///         data.mismatches(&odds)
+    ///             .assert_eq(&data.filter(|x| x % 2 == 0));
+    ///     });
+    /// }
+    /// ```
+    pub fn mismatches(&self, other: &Self) -> Collection<G, D, R>
+    where D: ::ExchangeData+Hashable,
+          R: ::ExchangeData+Hashable,
+          G::Timestamp: Lattice+Ord
+    {
+        self.concat(&other.negate())
+            .consolidate()
+    }
+
+    /// Assert if any record ever accumulates to a negative count.
+    ///
+    /// Collections used with set semantics should never have negative counts, and one usually indicates
+    /// that a record was retracted more often than it was inserted. As with `assert_empty`, the test is only
+    /// applied as the computation is run, and only at those times the computation reaches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///         scope.new_collection_from(1 .. 10).1
+    ///              .map(|x| x / 3)
+    ///              .assert_non_negative();
+    ///     });
+    /// }
+    /// ```
+    pub fn assert_non_negative(&self)
+    where D: ::ExchangeData+Hashable,
+          R: ::ExchangeData,
+          G::Timestamp: Lattice+Ord
+    {
+        use ::difference::Monoid;
+        use operators::reduce::Threshold;
+        self.threshold_named("AssertNonNegative", |record, count: &R| {
+            if count < &R::zero() {
+                panic!("Assertion failed: negative count {:?} for record {:?}", count, record);
+            }
+            R::zero()
+        });
     }
 }
 
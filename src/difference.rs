@@ -165,6 +165,213 @@ mod present {
     }
 }
 
+pub use self::checked::Checked;
+mod checked {
+
+    use super::{Semigroup, Monoid, Abelian, Multiply};
+
+    /// An integer difference whose arithmetic panics on overflow.
+    ///
+    /// The built-in integer implementations use the native operators, which wrap in release builds.
+    /// A wrapped accumulation is silently incorrect, as are all collections derived from it. This type
+    /// instead panics at the moment of overflow, which surfaces the problem where it happens.
+    #[derive(Abomonation, Copy, Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Serialize, Deserialize, Hash)]
+    pub struct Checked<T>(pub T);
+
+    /// Implementations for checked integers.
+    macro_rules! checked_implementation {
+        ($t:ty) => {
+            impl Semigroup for Checked<$t> {
+                #[inline] fn plus_equals(&mut self, rhs: &Self) {
+                    self.0 = self.0.checked_add(rhs.0).expect("overflow in difference addition");
+                }
+                #[inline] fn is_zero(&self) -> bool { self.0 == 0 }
+            }
+
+            impl Monoid for Checked<$t> {
+                #[inline] fn zero() -> Self { Checked(0) }
+            }
+
+            impl Multiply<Self> for Checked<$t> {
+                type Output = Self;
+                fn multiply(self, rhs: &Self) -> Self {
+                    Checked(self.0.checked_mul(rhs.0).expect("overflow in difference multiplication"))
+                }
+            }
+        };
+    }
+
+    macro_rules! checked_abelian_implementation {
+        ($t:ty) => {
+            impl Abelian for Checked<$t> {
+                #[inline] fn negate(self) -> Self {
+                    Checked(self.0.checked_neg().expect("overflow in difference negation"))
+                }
+            }
+        };
+    }
+
+    checked_implementation!(i8);
+    checked_implementation!(i16);
+    checked_implementation!(i32);
+    checked_implementation!(i64);
+    checked_implementation!(i128);
+    checked_implementation!(isize);
+    checked_implementation!(u8);
+    checked_implementation!(u16);
+    checked_implementation!(u32);
+    checked_implementation!(u64);
+    checked_implementation!(u128);
+    checked_implementation!(usize);
+
+    checked_abelian_implementation!(i8);
+    checked_abelian_implementation!(i16);
+    checked_abelian_implementation!(i32);
+    checked_abelian_implementation!(i64);
+    checked_abelian_implementation!(i128);
+    checked_abelian_implementation!(isize);
+}
+
+pub use self::fractional::{Float, Rational};
+mod fractional {
+
+    use std::cmp::Ordering;
+
+    use super::{Semigroup, Monoid, Abelian, Multiply};
+
+    /// A floating-point difference, which is zero when within a tolerance of zero.
+    ///
+    /// Floating-point addition is neither exact nor associative, and accumulations that should cancel
+    /// often leave a small residue. Such residues are treated as zero, so that the corresponding updates
+    /// can be retired, and `Float::TOLERANCE` bounds the magnitude of values considered zero.
+    ///
+    /// The type is ordered by `f64::total_cmp`, as required of all differences. This order is only used
+    /// to sort updates, and is not meaningful beyond that.
+    #[derive(Abomonation, Copy, Debug, Clone, Serialize, Deserialize)]
+    pub struct Float(pub f64);
+
+    impl Float {
+        /// The largest magnitude considered to be zero.
+        pub const TOLERANCE: f64 = 1e-9;
+    }
+
+    impl PartialEq for Float {
+        fn eq(&self, other: &Self) -> bool { self.cmp(other) == Ordering::Equal }
+    }
+    impl Eq for Float { }
+    impl PartialOrd for Float {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+    }
+    impl Ord for Float {
+        fn cmp(&self, other: &Self) -> Ordering { self.0.total_cmp(&other.0) }
+    }
+
+    impl Semigroup for Float {
+        #[inline] fn plus_equals(&mut self, rhs: &Self) { self.0 += rhs.0; }
+        #[inline] fn is_zero(&self) -> bool { self.0.abs() <= Float::TOLERANCE }
+    }
+
+    impl Monoid for Float {
+        #[inline] fn zero() -> Self { Float(0.0) }
+    }
+
+    impl Abelian for Float {
+        #[inline] fn negate(self) -> Self { Float(-self.0) }
+    }
+
+    impl Multiply<Self> for Float {
+        type Output = Self;
+        fn multiply(self, rhs: &Self) -> Self { Float(self.0 * rhs.0) }
+    }
+
+    /// An exact rational difference.
+    ///
+    /// Rationals are maintained in lowest terms with a positive denominator, so that equal values have
+    /// equal representations. Arithmetic panics if a reduced numerator or denominator overflows an `i64`.
+    #[derive(Abomonation, Copy, Eq, PartialEq, Debug, Clone, Serialize, Deserialize, Hash)]
+    pub struct Rational {
+        numerator: i64,
+        denominator: i64,
+    }
+
+    impl Rational {
+        /// Creates a rational from a numerator and a non-zero denominator.
+        pub fn new(numerator: i64, denominator: i64) -> Self {
+            assert!(denominator != 0, "rational with zero denominator");
+            Self::reduce(numerator as i128, denominator as i128)
+        }
+        /// The numerator, in lowest terms.
+        pub fn numerator(&self) -> i64 { self.numerator }
+        /// The denominator, in lowest terms and positive.
+        pub fn denominator(&self) -> i64 { self.denominator }
+
+        fn reduce(mut numerator: i128, mut denominator: i128) -> Self {
+            if denominator < 0 {
+                numerator = -numerator;
+                denominator = -denominator;
+            }
+            let (mut a, mut b) = (numerator.abs(), denominator);
+            while b != 0 {
+                let t = a % b;
+                a = b;
+                b = t;
+            }
+            if a > 1 {
+                numerator /= a;
+                denominator /= a;
+            }
+            use std::convert::TryFrom;
+            Rational {
+                numerator: i64::try_from(numerator).expect("overflow in rational numerator"),
+                denominator: i64::try_from(denominator).expect("overflow in rational denominator"),
+            }
+        }
+    }
+
+    impl From<i64> for Rational {
+        fn from(integer: i64) -> Self { Rational { numerator: integer, denominator: 1 } }
+    }
+
+    impl PartialOrd for Rational {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+    }
+    impl Ord for Rational {
+        fn cmp(&self, other: &Self) -> Ordering {
+            let this = (self.numerator as i128) * (other.denominator as i128);
+            let that = (other.numerator as i128) * (self.denominator as i128);
+            this.cmp(&that)
+        }
+    }
+
+    impl Semigroup for Rational {
+        fn plus_equals(&mut self, rhs: &Self) {
+            let numerator = (self.numerator as i128) * (rhs.denominator as i128) + (rhs.numerator as i128) * (self.denominator as i128);
+            let denominator = (self.denominator as i128) * (rhs.denominator as i128);
+            *self = Rational::reduce(numerator, denominator);
+        }
+        #[inline] fn is_zero(&self) -> bool { self.numerator == 0 }
+    }
+
+    impl Monoid for Rational {
+        #[inline] fn zero() -> Self { Rational { numerator: 0, denominator: 1 } }
+    }
+
+    impl Abelian for Rational {
+        #[inline] fn negate(self) -> Self {
+            Rational { numerator: self.numerator.checked_neg().expect("overflow in difference negation"), denominator: self.denominator }
+        }
+    }
+
+    impl Multiply<Self> for Rational {
+        type Output = Self;
+        fn multiply(self, rhs: &Self) -> Self {
+            let numerator = (self.numerator as i128) * (rhs.numerator as i128);
+            let denominator = (self.denominator as i128) * (rhs.denominator as i128);
+            Rational::reduce(numerator, denominator)
+        }
+    }
+}
+
 // Pair implementations.
 mod tuples {
 
@@ -271,3 +478,42 @@ mod vector {
         }
     }
 }
+
+mod array {
+
+    use super::{Semigroup, Monoid, Abelian, Multiply};
+
+    /// Implementations for fixed-size arrays, which accumulate component-wise.
+    ///
+    /// An array is zero only when all of its components are zero, which allows one collection to
+    /// maintain several accumulations at once, for example several counts or sums of each record.
+    impl<R: Semigroup, const N: usize> Semigroup for [R; N] {
+        #[inline] fn plus_equals(&mut self, rhs: &Self) {
+            for (update, other) in self.iter_mut().zip(rhs.iter()) {
+                update.plus_equals(other);
+            }
+        }
+        #[inline] fn is_zero(&self) -> bool {
+            self.iter().all(|x| x.is_zero())
+        }
+    }
+
+    impl<R: Monoid, const N: usize> Monoid for [R; N] {
+        #[inline] fn zero() -> Self {
+            std::array::from_fn(|_| R::zero())
+        }
+    }
+
+    impl<R: Abelian, const N: usize> Abelian for [R; N] {
+        #[inline] fn negate(self) -> Self {
+            self.map(|x| x.negate())
+        }
+    }
+
+    impl<T, R: Multiply<T>, const N: usize> Multiply<T> for [R; N] {
+        type Output = [<R as Multiply<T>>::Output; N];
+        #[inline] fn multiply(self, rhs: &T) -> Self::Output {
+            self.map(|x| x.multiply(rhs))
+        }
+    }
+}
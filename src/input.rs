@@ -189,6 +189,7 @@ impl<G: TimelyInput> Input for G where <G as ScopeParent>::Timestamp: Lattice {
 pub struct InputSession<T: Timestamp+Clone, D: Data, R: Semigroup> {
     time: T,
     buffer: Vec<(D, T, R)>,
+    capacity: usize,
     handle: Handle<T,(D,T,R)>,
 }
 
@@ -228,11 +229,19 @@ impl<T: Timestamp+Clone, D: Data, R: Semigroup> InputSession<T, D, R> {
     /// Allocates a new input handle.
     pub fn new() -> Self {
         let handle: Handle<T,_> = Handle::new();
-        InputSession {
-            time: handle.time().clone(),
-            buffer: Vec::new(),
-            handle,
-        }
+        Self::from(handle)
+    }
+
+    /// Allocates a new input handle whose internal buffer holds `capacity` updates.
+    ///
+    /// The buffer is sent to timely dataflow each time it fills, so larger capacities
+    /// mean fewer, larger batches when loading datasets of known size.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let handle: Handle<T,_> = Handle::new();
+        let mut session = Self::from(handle);
+        session.capacity = capacity;
+        session.buffer.reserve(capacity);
+        session
     }
 
     /// Creates a new session from a reference to an input handle.
@@ -240,6 +249,7 @@ impl<T: Timestamp+Clone, D: Data, R: Semigroup> InputSession<T, D, R> {
         InputSession {
             time: handle.time().clone(),
             buffer: Vec::new(),
+            capacity: 1024,
             handle,
         }
     }
@@ -250,8 +260,7 @@ impl<T: Timestamp+Clone, D: Data, R: Semigroup> InputSession<T, D, R> {
             if self.buffer.len() > 0 {
                 self.handle.send_batch(&mut self.buffer);
             }
-            // TODO : This is a fairly arbitrary choice; should probably use `Context::default_size()` or such.
-            self.buffer.reserve(1024);
+            self.buffer.reserve(self.capacity);
         }
         self.buffer.push((element, self.time.clone(), change));
     }
@@ -263,8 +272,7 @@ impl<T: Timestamp+Clone, D: Data, R: Semigroup> InputSession<T, D, R> {
             if self.buffer.len() > 0 {
                 self.handle.send_batch(&mut self.buffer);
             }
-            // TODO : This is a fairly arbitrary choice; should probably use `Context::default_size()` or such.
-            self.buffer.reserve(1024);
+            self.buffer.reserve(self.capacity);
         }
         self.buffer.push((element, time, change));
     }
@@ -281,6 +289,29 @@ impl<T: Timestamp+Clone, D: Data, R: Semigroup> InputSession<T, D, R> {
         }
     }
 
+    /// Flushes the session, and then steps `worker` while traces exceed a budget of `limit` updates.
+    ///
+    /// This provides backpressure to the producer of input, which should call this method in place of
+    /// `flush` to avoid introducing more input while arrangements are large. The worker is stepped
+    /// only until `probe` has caught up with the session's time, as past that point no further work
+    /// can reduce the size of the arrangements, and waiting would stall indefinitely.
+    ///
+    /// The sizes of traces are reported by `registry`, which should be enabled for `worker` before
+    /// its dataflows are constructed. Loggers enabled afterwards, for example by `enable_plan`, are
+    /// composed with the registry and do not stop it from observing the traces.
+    pub fn flush_within<A: ::timely::communication::Allocate>(
+        &mut self,
+        worker: &mut ::timely::worker::Worker<A>,
+        probe: &::timely::dataflow::ProbeHandle<T>,
+        registry: &::logging::MetricsRegistry,
+        limit: usize,
+    ) {
+        self.flush();
+        while registry.size() > limit && probe.less_than(&self.time) {
+            worker.step();
+        }
+    }
+
     /// Advances the logical time for future records.
     ///
     /// Importantly, this method does **not** immediately inform timely dataflow of the change. This happens only when
@@ -89,6 +89,25 @@ pub trait Data : timely::Data + Ord + Debug { }
 impl<T: timely::Data + Ord + Debug> Data for T { }
 
 /// Data types exchangeable in differential dataflow.
+///
+/// This trait, along with `Data` and `Hashable`, is implemented automatically for all types with the
+/// required bounds, and so user record types only need the corresponding standard derives:
+///
+/// ```
+/// #[macro_use] extern crate serde_derive;
+/// extern crate differential_dataflow;
+///
+/// use differential_dataflow::{ExchangeData, Hashable};
+///
+/// #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+/// struct Edge { src: u32, dst: u32 }
+///
+/// fn requires<T: ExchangeData+Hashable>() { }
+///
+/// fn main() {
+///     requires::<Edge>();
+/// }
+/// ```
 pub trait ExchangeData : timely::ExchangeData + Ord + Debug { }
 impl<T: timely::ExchangeData + Ord + Debug> ExchangeData for T { }
 
@@ -114,6 +133,7 @@ pub mod collection;
 pub mod logging;
 pub mod consolidation;
 pub mod capture;
+pub mod reference;
 
 /// Configuration options for differential dataflow.
 #[derive(Default)]
@@ -125,7 +145,17 @@ pub struct Config {
     /// cause these operators to reschedule themselves as long as their arrangemnt has not
     /// reached a compact representation, and each scheduling quantum they will perform
     /// compaction work as if `effort` records had been added to the arrangement.
-    pub idle_merge_effort: Option<isize>
+    ///
+    /// Once an arrangement reaches a compact representation its operator stops rescheduling
+    /// itself, and workers with no other work may park (e.g. with `Worker::step_or_park`).
+    pub idle_merge_effort: Option<isize>,
+    /// An amount of join output to produce each scheduling quantum.
+    ///
+    /// The default value of `None` uses an amount of one million records. Join operators that
+    /// have more work outstanding than this will yield and reschedule themselves, which allows
+    /// other operators sharing the worker to run, at the cost of throughput. Smaller values
+    /// improve the responsiveness of the worker when joins produce large amounts of output.
+    pub join_fuel: Option<usize>,
 }
 
 impl Config {
@@ -134,6 +164,11 @@ impl Config {
         self.idle_merge_effort = effort;
         self
     }
+    /// Assign an amount of output for join operators to produce each scheduling quantum.
+    pub fn join_fuel(mut self, fuel: Option<usize>) -> Self {
+        self.join_fuel = fuel;
+        self
+    }
 }
 
 /// Introduces differential options to a timely configuration.
@@ -141,4 +176,7 @@ pub fn configure(config: &mut timely::WorkerConfig, options: &Config) {
     if let Some(effort) = options.idle_merge_effort {
         config.set("differential/idle_merge_effort".to_string(), effort);
     }
+    if let Some(fuel) = options.join_fuel {
+        config.set("differential/join_fuel".to_string(), fuel);
+    }
 }
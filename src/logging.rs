@@ -10,10 +10,365 @@ where
     W: std::io::Write+'static,
 {
     let writer = ::timely::dataflow::operators::capture::EventWriter::new(writer);
-    let mut logger = ::timely::logging::BatchLogger::new(writer);
+    enable_with(worker, writer)
+}
+
+/// Enables logging of differential dataflow events to an arbitrary event pusher.
+///
+/// Events are published as a timely dataflow stream, timestamped by the elapsed time at which they
+/// were logged. Using an `EventLink` as the pusher allows the events to be replayed into another
+/// dataflow with `replay_into`, where they can be analyzed with differential dataflow itself.
+pub fn enable_with<A, P>(worker: &mut timely::worker::Worker<A>, pusher: P) -> Option<Box<dyn std::any::Any+'static>>
+where
+    A: timely::communication::Allocate,
+    P: ::timely::dataflow::operators::capture::EventPusher<std::time::Duration, (std::time::Duration, usize, DifferentialEvent)>+'static,
+{
+    let mut logger = ::timely::logging::BatchLogger::new(pusher);
+    insert_composed::<_, DifferentialEvent, _>(worker, "differential/arrange", move |time, data| logger.publish_batch(time, data))
+}
+
+/// Registers `action` for the events logged under `name`, alongside any logger already registered.
+///
+/// Only one logger can be registered for each name, and so the events are also forwarded to the
+/// prior logger, if any. This allows `enable`, `enable_metrics`, and `enable_plan` to be combined.
+fn insert_composed<A, T, F>(worker: &mut timely::worker::Worker<A>, name: &str, mut action: F) -> Option<Box<dyn std::any::Any+'static>>
+where
+    A: timely::communication::Allocate,
+    T: Clone+'static,
+    F: FnMut(&std::time::Duration, &mut Vec<(std::time::Duration, usize, T)>)+'static,
+{
+    let mut prior = worker.log_register().get::<T>(name);
     worker
         .log_register()
-        .insert::<DifferentialEvent,_>("differential/arrange", move |time, data| logger.publish_batch(time, data))
+        .insert::<T,_>(name, move |time, data| {
+            if let Some(ref mut prior) = prior {
+                prior.log_many(data.iter().map(|x| x.2.clone()));
+                prior.flush();
+            }
+            action(time, data);
+        })
+}
+
+/// Enables the accumulation of per-operator metrics from timely and differential dataflow events.
+///
+/// The returned registry is updated as events are logged, which happens as the worker runs, and it
+/// can be read at any point to report the state of each operator. Events are also forwarded to any
+/// loggers previously registered, for example by `enable` or `enable_plan`. The registry should be
+/// enabled before dataflows are constructed, as operators acquire their loggers when constructed.
+pub fn enable_metrics<A>(worker: &mut timely::worker::Worker<A>) -> MetricsRegistry
+where
+    A: timely::communication::Allocate,
+{
+    use timely::logging::TimelyEvent;
+
+    let registry = MetricsRegistry::default();
+
+    let observer = registry.clone();
+    insert_composed::<_, TimelyEvent, _>(worker, "timely", move |_time, data| {
+        for (_time, _worker, event) in data.iter() {
+            observer.observe_timely(event);
+        }
+    });
+
+    let observer = registry.clone();
+    insert_composed::<_, DifferentialEvent, _>(worker, "differential/arrange", move |_time, data| {
+        for (_time, _worker, event) in data.iter() {
+            observer.observe(event);
+        }
+    });
+
+    registry
+}
+
+/// Counters accumulated for a single operator.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct OperatorMetrics {
+    /// Number of records received by the operator, on all of its inputs.
+    pub records_in: usize,
+    /// Number of records sent by the operator, on all of its outputs.
+    pub records_out: usize,
+    /// Number of batches introduced to the operator's trace.
+    pub batches: usize,
+    /// Number of updates in batches introduced to the operator's trace.
+    pub records: usize,
+    /// Number of updates currently held by the operator's trace.
+    pub size: isize,
+    /// Number of completed merges.
+    pub merges: usize,
+    /// Number of updates presented to completed merges.
+    pub merge_work: usize,
+    /// Total work by which merges fell short of completing in time.
+    pub shortfall: usize,
+    /// Number of outstanding handles to the operator's trace.
+    pub shares: isize,
+}
+
+/// A registry of per-operator metrics, indexed by operator identifier.
+///
+/// Clones of the registry share the same metrics.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRegistry {
+    metrics: ::std::rc::Rc<::std::cell::RefCell<::std::collections::HashMap<usize, OperatorMetrics>>>,
+    topology: ::std::rc::Rc<::std::cell::RefCell<Topology>>,
+}
+
+/// Operator identifiers and channel endpoints, used to attribute messages to operators.
+#[derive(Debug, Default)]
+struct Topology {
+    ids: ::std::collections::HashMap<Vec<usize>, usize>,
+    channels: ::std::collections::HashMap<usize, (Vec<usize>, Vec<usize>)>,
+}
+
+impl MetricsRegistry {
+    /// Updates metrics to reflect a timely dataflow event.
+    ///
+    /// Records are counted as sent by the source operator of a channel, and as received by its
+    /// target operator, on the workers that send and receive them respectively.
+    pub fn observe_timely(&self, event: &::timely::logging::TimelyEvent) {
+        use timely::logging::TimelyEvent;
+        let mut topology = self.topology.borrow_mut();
+        match event {
+            TimelyEvent::Operates(x) => {
+                topology.ids.insert(x.addr.clone(), x.id);
+            },
+            TimelyEvent::Channels(x) => {
+                let mut source = x.scope_addr.clone();
+                if x.source.0 > 0 { source.push(x.source.0); }
+                let mut target = x.scope_addr.clone();
+                if x.target.0 > 0 { target.push(x.target.0); }
+                topology.channels.insert(x.id, (source, target));
+            },
+            TimelyEvent::Messages(x) => {
+                if let Some((source, target)) = topology.channels.get(&x.channel) {
+                    let addr = if x.is_send { source } else { target };
+                    if let Some(id) = topology.ids.get(addr) {
+                        let mut metrics = self.metrics.borrow_mut();
+                        let entry = metrics.entry(*id).or_insert_with(Default::default);
+                        if x.is_send { entry.records_out += x.length; }
+                        else { entry.records_in += x.length; }
+                    }
+                }
+            },
+            _ => { },
+        }
+    }
+    /// Updates metrics to reflect a differential dataflow event.
+    pub fn observe(&self, event: &DifferentialEvent) {
+        let mut metrics = self.metrics.borrow_mut();
+        match event {
+            DifferentialEvent::Batch(x) => {
+                let entry = metrics.entry(x.operator).or_insert_with(Default::default);
+                entry.batches += 1;
+                entry.records += x.length;
+                entry.size += x.length as isize;
+            },
+            DifferentialEvent::Merge(x) => {
+                if let Some(length) = x.complete {
+                    let entry = metrics.entry(x.operator).or_insert_with(Default::default);
+                    entry.merges += 1;
+                    entry.merge_work += x.length1 + x.length2;
+                    entry.size += length as isize - (x.length1 + x.length2) as isize;
+                }
+            },
+            DifferentialEvent::Drop(x) => {
+                let entry = metrics.entry(x.operator).or_insert_with(Default::default);
+                entry.size -= x.length as isize;
+            },
+            DifferentialEvent::MergeShortfall(x) => {
+                let entry = metrics.entry(x.operator).or_insert_with(Default::default);
+                entry.shortfall += x.shortfall;
+            },
+            DifferentialEvent::TraceShare(x) => {
+                let entry = metrics.entry(x.operator).or_insert_with(Default::default);
+                entry.shares += x.diff;
+            },
+            DifferentialEvent::TraceType(_) => { },
+        }
+    }
+    /// The metrics for an operator, if any events have been observed for it.
+    pub fn get(&self, operator: usize) -> Option<OperatorMetrics> {
+        self.metrics.borrow().get(&operator).cloned()
+    }
+    /// The total number of updates held by all traces.
+    pub fn size(&self) -> usize {
+        let total: isize = self.metrics.borrow().values().map(|x| x.size).sum();
+        if total > 0 { total as usize } else { 0 }
+    }
+    /// The metrics for all operators, ordered by operator identifier.
+    pub fn snapshot(&self) -> Vec<(usize, OperatorMetrics)> {
+        let mut result: Vec<_> = self.metrics.borrow().iter().map(|(k, v)| (*k, v.clone())).collect();
+        result.sort_by(|x, y| x.0.cmp(&y.0));
+        result
+    }
+}
+
+/// Enables the recording of the dataflow plan, as operators and channels are constructed.
+///
+/// The plan must be enabled before dataflows are constructed, as it is assembled from the events
+/// logged during construction. Arrangements record the number of handles to their traces, which
+/// indicates when they are shared, and the key and value types of their traces. Events are also
+/// forwarded to any loggers previously registered, for example by `enable` or `enable_metrics`.
+pub fn enable_plan<A>(worker: &mut timely::worker::Worker<A>) -> Plan
+where
+    A: timely::communication::Allocate,
+{
+    use timely::logging::TimelyEvent;
+
+    let plan = Plan::default();
+
+    let observer = plan.clone();
+    insert_composed::<_, TimelyEvent, _>(worker, "timely", move |_time, data| {
+        let mut inner = observer.inner.borrow_mut();
+        for (_time, _worker, event) in data.iter() {
+            match event {
+                TimelyEvent::Operates(x) => {
+                    inner.ids.insert(x.id, x.addr.clone());
+                    inner.operators.insert(x.addr.clone(), x.name.clone());
+                },
+                TimelyEvent::Channels(x) => {
+                    let mut source = x.scope_addr.clone();
+                    if x.source.0 > 0 { source.push(x.source.0); }
+                    let mut target = x.scope_addr.clone();
+                    if x.target.0 > 0 { target.push(x.target.0); }
+                    inner.channels.push((source, target));
+                },
+                _ => { },
+            }
+        }
+    });
+
+    let observer = plan.clone();
+    insert_composed::<_, DifferentialEvent, _>(worker, "differential/arrange", move |_time, data| {
+        let mut inner = observer.inner.borrow_mut();
+        for (_time, _worker, event) in data.iter() {
+            match event {
+                DifferentialEvent::TraceShare(x) => {
+                    *inner.shares.entry(x.operator).or_insert(0) += x.diff;
+                },
+                DifferentialEvent::TraceType(x) => {
+                    inner.types.insert(x.operator, (x.key.clone(), x.val.clone()));
+                },
+                _ => { },
+            }
+        }
+    });
+
+    plan
+}
+
+/// A record of the operators and channels of constructed dataflows.
+///
+/// Operators are identified by their addresses, the sequence of operator indices from the root
+/// of the worker through each containing scope. Clones of the plan share the same record.
+#[derive(Debug, Clone, Default)]
+pub struct Plan {
+    inner: ::std::rc::Rc<::std::cell::RefCell<PlanInner>>,
+}
+
+#[derive(Debug, Default)]
+struct PlanInner {
+    ids: ::std::collections::HashMap<usize, Vec<usize>>,
+    operators: ::std::collections::BTreeMap<Vec<usize>, String>,
+    channels: Vec<(Vec<usize>, Vec<usize>)>,
+    shares: ::std::collections::HashMap<usize, isize>,
+    types: ::std::collections::HashMap<usize, (String, String)>,
+}
+
+impl PlanInner {
+    /// The number of trace handles for each operator address with an arrangement.
+    fn shares(&self) -> ::std::collections::HashMap<&[usize], isize> {
+        self.shares
+            .iter()
+            .filter_map(|(id, count)| self.ids.get(id).map(|addr| (&addr[..], *count)))
+            .collect()
+    }
+    /// The key and value types of the trace for each operator address with an arrangement.
+    fn types(&self) -> ::std::collections::HashMap<&[usize], (&str, &str)> {
+        self.types
+            .iter()
+            .filter_map(|(id, (key, val))| self.ids.get(id).map(|addr| (&addr[..], (&key[..], &val[..]))))
+            .collect()
+    }
+}
+
+/// Renders `text` as a JSON string literal.
+fn json_string(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+impl Plan {
+    /// Renders the plan in the Graphviz DOT language.
+    ///
+    /// Operators are nodes labeled by their names, with the key and value types and the number of
+    /// trace handles appended for those operators that maintain arrangements, and channels are edges
+    /// between them.
+    pub fn to_dot(&self) -> String {
+        let inner = self.inner.borrow();
+        let shares = inner.shares();
+        let types = inner.types();
+        let mut result = String::from("digraph {\n");
+        for (addr, name) in inner.operators.iter() {
+            let mut label = name.clone();
+            if let Some((key, val)) = types.get(&addr[..]) {
+                label.push_str(&format!(" ({} => {})", key, val));
+            }
+            if let Some(count) = shares.get(&addr[..]) {
+                label.push_str(&format!(" (shared by {})", count));
+            }
+            result.push_str(&format!("    \"{:?}\" [label={}];\n", addr, json_string(&label)));
+        }
+        for (source, target) in inner.channels.iter() {
+            result.push_str(&format!("    \"{:?}\" -> \"{:?}\";\n", source, target));
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    /// Renders the plan as JSON.
+    ///
+    /// The result is an object with `operators`, each with an `addr`, a `name`, and for arrangements
+    /// `key` and `val` type names and a count of `shares`, and `channels`, each with a `source` and
+    /// `target` address.
+    pub fn to_json(&self) -> String {
+        let inner = self.inner.borrow();
+        let shares = inner.shares();
+        let types = inner.types();
+        let operators: Vec<String> =
+        inner.operators
+            .iter()
+            .map(|(addr, name)| {
+                let mut result = format!("{{\"addr\":{:?},\"name\":{}", addr, json_string(name));
+                if let Some((key, val)) = types.get(&addr[..]) {
+                    result.push_str(&format!(",\"key\":{},\"val\":{}", json_string(key), json_string(val)));
+                }
+                if let Some(count) = shares.get(&addr[..]) {
+                    result.push_str(&format!(",\"shares\":{}", count));
+                }
+                result.push('}');
+                result
+            })
+            .collect();
+        let channels: Vec<String> =
+        inner.channels
+            .iter()
+            .map(|(source, target)| format!("{{\"source\":{:?},\"target\":{:?}}}", source, target))
+            .collect();
+        format!("{{\"operators\":[{}],\"channels\":[{}]}}", operators.join(","), channels.join(","))
+    }
 }
 
 /// Possible different differential events.
@@ -29,6 +384,8 @@ pub enum DifferentialEvent {
     MergeShortfall(MergeShortfall),
     /// Trace sharing event.
     TraceShare(TraceShare),
+    /// Key and value types of a trace.
+    TraceType(TraceType),
 }
 
 /// Either the start or end of a merge event.
@@ -94,3 +451,16 @@ pub struct TraceShare {
 }
 
 impl From<TraceShare> for DifferentialEvent { fn from(e: TraceShare) -> Self { DifferentialEvent::TraceShare(e) } }
+
+/// The key and value types of a trace, logged when the trace is created.
+#[derive(Debug, Clone, Abomonation, Ord, PartialOrd, Eq, PartialEq)]
+pub struct TraceType {
+    /// Operator identifier.
+    pub operator: usize,
+    /// Name of the key type.
+    pub key: String,
+    /// Name of the value type.
+    pub val: String,
+}
+
+impl From<TraceType> for DifferentialEvent { fn from(e: TraceType) -> Self { DifferentialEvent::TraceType(e) } }
@@ -3,17 +3,21 @@
 use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 use std::collections::VecDeque;
+use std::hash::Hash;
 
 use timely::dataflow::Scope;
 use timely::dataflow::operators::generic::source;
 use timely::progress::Timestamp;
 use timely::progress::{Antichain, frontier::AntichainRef};
-use timely::dataflow::operators::CapabilitySet;
+use timely::dataflow::operators::{CapabilitySet, Filter};
 
+use ::{Collection, AsCollection, ExchangeData};
+use ::difference::Semigroup;
 use lattice::Lattice;
-use trace::{Trace, TraceReader, Batch, BatchReader, Cursor};
+use trace::{Trace, TraceReader, Batch, BatchReader, Cursor, Statistics};
 
-use trace::wrappers::rc::TraceBox;
+use trace::wrappers::rc::{TraceBox, CompactionPolicy};
+use trace::implementations::spine_fueled::Spine;
 
 use timely::scheduling::Activator;
 
@@ -27,6 +31,12 @@ use crate::trace::wrappers::frontier::{TraceFrontier, BatchFrontier};
 ///
 /// The `TraceAgent` is the default trace type produced by `arranged`, and it can be extracted
 /// from the dataflow in which it was defined, and imported into other dataflows.
+///
+/// The shared trace is owned by its agents, and the operator maintaining it holds only a weak
+/// reference. When the last agent is dropped, including those held by operators that import or
+/// read from the arrangement, the trace and all of its batches are released immediately, and the
+/// operator stops maintaining it. The operator continues to produce batches for its output stream
+/// until its dataflow completes or is dropped, but these are not retained.
 pub struct TraceAgent<Tr>
 where
     Tr: TraceReader,
@@ -81,6 +91,7 @@ where
         self.trace.borrow_mut().trace.cursor_through(frontier)
     }
     fn map_batches<F: FnMut(&Self::Batch)>(&self, f: F) { self.trace.borrow().trace.map_batches(f) }
+    fn statistics(&mut self) -> Statistics { self.trace.borrow_mut().trace.statistics() }
 }
 
 impl<Tr> TraceAgent<Tr>
@@ -101,6 +112,13 @@ where
             logging.log(
                 ::logging::TraceShare { operator: operator.global_id, diff: 1 }
             );
+            logging.log(
+                ::logging::TraceType {
+                    operator: operator.global_id,
+                    key: ::std::any::type_name::<Tr::Key>().to_owned(),
+                    val: ::std::any::type_name::<Tr::Val>().to_owned(),
+                }
+            );
         }
 
         let reader = TraceAgent {
@@ -122,6 +140,47 @@ where
         (reader, writer)
     }
 
+    /// Sets the policy for compacting the shared trace.
+    ///
+    /// The policy applies to the trace as a whole, rather than to this handle, and it only
+    /// affects future compaction. By default, traces are compacted lazily as batches merge.
+    pub fn set_compaction_policy(&mut self, policy: CompactionPolicy) {
+        self.trace.borrow_mut().set_policy(policy);
+    }
+
+    /// Releases excess capacity held by the shared trace.
+    ///
+    /// This is most useful after a bulk load completes, when the trace's batches were assembled
+    /// with allocations sized for merging and are not expected to change soon.
+    pub fn shrink_to_fit(&mut self)
+    where
+        Tr: Trace,
+        Tr::Batch: Batch,
+    {
+        self.trace.borrow_mut().trace.shrink_to_fit();
+    }
+
+    /// Compacts the shared trace to its logical compaction frontier immediately, as for `Trace::purge`.
+    ///
+    /// The logical frontier of the shared trace reflects all of its handles, and so updates are only
+    /// consolidated to the extent that every handle allows.
+    pub fn purge(&mut self)
+    where
+        Tr: Trace,
+        Tr::Batch: Batch,
+    {
+        self.trace.borrow_mut().trace.purge();
+    }
+
+    /// Reports the heap allocations of the shared trace, as `(length, capacity)` pairs in bytes.
+    pub fn heap_size<F: FnMut(usize, usize)>(&self, callback: F)
+    where
+        Tr: Trace,
+        Tr::Batch: Batch,
+    {
+        self.trace.borrow().trace.heap_size(callback);
+    }
+
     /// Attaches a new shared queue to the trace.
     ///
     /// The queue is first populated with existing batches from the trace,
@@ -157,6 +216,54 @@ where
     }
 }
 
+impl<B> TraceAgent<Spine<B>>
+where
+    B: Batch+Clone+'static,
+    B::Key: Ord+Clone,
+    B::Val: Ord+Clone,
+    B::Time: Lattice+Timestamp+Ord+Clone+::std::fmt::Debug,
+    B::R: Semigroup,
+{
+    /// Forks the shared trace, returning a handle to the fork and a writer for its subsequent batches.
+    ///
+    /// The fork shares the batches of the trace without copying their updates, and it is compacted
+    /// no further than this handle allows. Batches written to the fork are private to it, as are the
+    /// batches of the original trace written after the fork. The fork can be imported into dataflows
+    /// like any other trace; dropping the writer seals the fork, after which those dataflows complete
+    /// once they have read its contents.
+    pub fn fork(&self) -> (Self, TraceWriter<Spine<B>>) {
+
+        let (trace, policy) = {
+            let borrow = self.trace.borrow();
+            (borrow.trace.fork(), borrow.policy)
+        };
+
+        let mut upper = Antichain::from_elem(<B::Time as Timestamp>::minimum());
+        trace.map_batches(|batch| upper.clone_from(batch.upper()));
+
+        let mut trace_box = TraceBox::new(trace);
+        trace_box.set_policy(policy);
+        let trace = Rc::new(RefCell::new(trace_box));
+        let queues = Rc::new(RefCell::new(Vec::new()));
+
+        let mut fork = TraceAgent {
+            trace: trace.clone(),
+            queues: Rc::downgrade(&queues),
+            logical_compaction: trace.borrow().logical_compaction.frontier().to_owned(),
+            physical_compaction: trace.borrow().physical_compaction.frontier().to_owned(),
+            temp_antichain: Antichain::new(),
+            operator: self.operator.clone(),
+            logging: None,
+        };
+        fork.set_logical_compaction(self.logical_compaction.borrow());
+        fork.set_physical_compaction(self.physical_compaction.borrow());
+
+        let writer = TraceWriter::new(upper.elements().to_vec(), Rc::downgrade(&trace), queues);
+
+        (fork, writer)
+    }
+}
+
 impl<Tr> TraceAgent<Tr>
 where
     Tr: TraceReader+'static,
@@ -498,6 +605,73 @@ where
 
         (Arranged { stream, trace }, shutdown_button.unwrap())
     }
+
+    /// Subscribes to the contents of the trace, as a snapshot followed by subsequent changes.
+    ///
+    /// The returned collection first presents the accumulated contents of the trace as updates at the
+    /// subscription frontier, which is the trace's current upper frontier (or its logical compaction
+    /// frontier, if that is later or the trace is already complete). It then presents each subsequent
+    /// change to the trace. Updates are consolidated at each time, so that each record appears at most
+    /// once in the snapshot, and the snapshot and changes together are exactly the contents of the trace.
+    pub fn subscribe<G>(&mut self, scope: &G, name: &str) -> (Collection<G, (Tr::Key, Tr::Val), Tr::R>, ShutdownButton<CapabilitySet<Tr::Time>>)
+    where
+        G: Scope<Timestamp=Tr::Time>,
+        Tr::Time: Timestamp,
+        Tr::Key: ExchangeData+Hash,
+        Tr::Val: ExchangeData+Hash,
+        Tr::R: ExchangeData+Semigroup,
+    {
+        let mut upper = Antichain::new();
+        self.read_upper(&mut upper);
+        let since = if upper.elements().is_empty() {
+            self.get_logical_compaction().to_owned()
+        }
+        else {
+            ::lattice::antichain_join(&self.get_logical_compaction()[..], upper.elements())
+        };
+
+        let (arranged, button) = self.import_frontier_core(scope, name, since, Antichain::new());
+        let collection = arranged
+            .as_collection(|key, val| (key.clone(), val.clone()))
+            .consolidate_complete();
+
+        (collection, button)
+    }
+
+    /// Imports the accumulated contents of the trace as of `time`, as a collection at `time`.
+    ///
+    /// The returned collection contains each `(key, val)` whose updates at times less or equal to
+    /// `time` accumulate to a non-zero difference, presented as updates at `time` and consolidated.
+    /// Updates at other times are suppressed, and so the collection does not change after `time`.
+    /// Its contents are complete once the trace's upper frontier is beyond `time`, and pressing the
+    /// returned button then releases the imported trace.
+    ///
+    /// The trace can only report accurate accumulations for times beyond its logical compaction
+    /// frontier, and `None` is returned for other times.
+    pub fn as_of<G>(&mut self, scope: &G, name: &str, time: Tr::Time) -> Option<(Collection<G, (Tr::Key, Tr::Val), Tr::R>, ShutdownButton<CapabilitySet<Tr::Time>>)>
+    where
+        G: Scope<Timestamp=Tr::Time>,
+        Tr::Time: Timestamp,
+        Tr::Key: ExchangeData+Hash,
+        Tr::Val: ExchangeData+Hash,
+        Tr::R: ExchangeData+Semigroup,
+    {
+        if !self.get_logical_compaction().less_equal(&time) {
+            return None;
+        }
+
+        // Advancing by `time` brings exactly the updates at times less or equal to `time` to `time`.
+        let since = Antichain::from_elem(time.clone());
+        let (arranged, button) = self.import_frontier_core(scope, name, since, Antichain::new());
+        let collection = arranged
+            .as_collection(|key, val| (key.clone(), val.clone()))
+            .inner
+            .filter(move |(_, t, _)| t == &time)
+            .as_collection()
+            .consolidate_complete();
+
+        Some((collection, button))
+    }
 }
 
 
@@ -24,7 +24,7 @@ use timely::dataflow::operators::generic::Operator;
 use timely::dataflow::channels::pact::{ParallelizationContract, Pipeline, Exchange};
 use timely::progress::Timestamp;
 use timely::progress::{Antichain, frontier::AntichainRef};
-use timely::dataflow::operators::Capability;
+use timely::dataflow::operators::{Capability, CapabilitySet};
 
 use ::{Data, ExchangeData, Collection, AsCollection, Hashable};
 use ::difference::Semigroup;
@@ -255,6 +255,171 @@ where
         .as_collection()
     }
 
+    /// Maintains a secondary index of the arrangement, keyed by a function of its keys and values.
+    ///
+    /// The result is an arrangement of `(key, val)` pairs indexed by `logic(key, val)`. It is derived
+    /// from the stream of batches that populate `self`, and so it reflects exactly the same updates,
+    /// at the same times, as the primary arrangement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    /// use differential_dataflow::operators::arrange::ArrangeByKey;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         // orders as (customer, product) pairs.
+    ///         let orders = scope.new_collection_from(vec![(0, 'a'), (1, 'a'), (1, 'b')]).1;
+    ///
+    ///         // index the orders by customer, and then by product.
+    ///         let by_customer = orders.arrange_by_key();
+    ///         let by_product = by_customer.index_by(|_customer, product| *product);
+    ///
+    ///         by_product
+    ///             .as_collection(|product, order| (*product, *order))
+    ///             .assert_eq(&orders.map(|(c, p)| (p, (c, p))));
+    ///     });
+    /// }
+    /// ```
+    pub fn index_by<K2, L>(&self, mut logic: L) -> Arranged<G, TraceAgent<DefaultValTrace<K2, (Tr::Key, Tr::Val), G::Timestamp, Tr::R>>>
+    where
+        Tr::Key: ExchangeData,
+        Tr::Val: ExchangeData,
+        Tr::R: ExchangeData+Semigroup,
+        K2: ExchangeData+Hashable,
+        L: FnMut(&Tr::Key, &Tr::Val) -> K2+'static,
+    {
+        self.as_collection(move |key, val| (logic(key, val), (key.clone(), val.clone())))
+            .arrange_by_key_named("IndexBy")
+    }
+
+    /// Maintains a copy of the accumulated contents that can be read from other threads.
+    ///
+    /// The returned `SharedTrace` is updated by this worker as batches are produced, and each read
+    /// reports the frontier through which its results are complete. See the `shared` module for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    /// use differential_dataflow::operators::arrange::ArrangeByKey;
+    ///
+    /// fn main() {
+    ///     let shared = ::timely::example(|scope| {
+    ///         scope.new_collection_from(vec![(0, 'a'), (1, 'b')]).1
+    ///              .arrange_by_key()
+    ///              .share()
+    ///     });
+    ///
+    ///     let reader = ::std::thread::spawn(move || shared.get(&1));
+    ///     let (frontier, vals) = reader.join().unwrap();
+    ///     assert!(frontier.is_empty());
+    ///     assert_eq!(vals, vec![('b', 1)]);
+    /// }
+    /// ```
+    pub fn share(&self) -> super::SharedTrace<Tr::Key, Tr::Val, G::Timestamp, Tr::R>
+    where
+        Tr::Key: Ord+Clone,
+        Tr::Val: Ord+Clone,
+        Tr::R: Semigroup,
+    {
+        super::shared::share(self)
+    }
+
+    /// Constructs a custom operator with managed access to the arrangement.
+    ///
+    /// The `logic` closure is called with newly received batches, a handle to the trace, the current
+    /// input frontier, and a buffer for output updates, whenever batches arrive or the frontier advances.
+    /// The trace handle is owned by the operator, and its compaction frontiers follow the input frontier,
+    /// so that state is accumulated as of the times the operator may still need to distinguish.
+    ///
+    /// Output updates must be at times greater or equal to the input frontier of the *previous* call,
+    /// for which the operator retains capabilities; this includes the times of all received batches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    /// use differential_dataflow::operators::arrange::ArrangeByKey;
+    /// use differential_dataflow::trace::{BatchReader, Cursor};
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let data = scope.new_collection_from(vec![(0, 'a'), (1, 'b')]).1;
+    ///
+    ///         // reproduce the updates of each received batch.
+    ///         data.arrange_by_key()
+    ///             .unary_trace("Replay", |batches, _trace, _frontier, output| {
+    ///                 for batch in batches.iter() {
+    ///                     let mut cursor = batch.cursor();
+    ///                     while let Some(key) = cursor.get_key(batch) {
+    ///                         while let Some(val) = cursor.get_val(batch) {
+    ///                             cursor.map_times(batch, |t, r| output.push(((*key, *val), *t, *r)));
+    ///                             cursor.step_val(batch);
+    ///                         }
+    ///                         cursor.step_key(batch);
+    ///                     }
+    ///                 }
+    ///             })
+    ///             .assert_eq(&data);
+    ///     });
+    /// }
+    /// ```
+    pub fn unary_trace<D, R2, L>(&self, name: &str, mut logic: L) -> Collection<G, D, R2>
+    where
+        Tr: 'static,
+        D: Data,
+        R2: Semigroup,
+        L: FnMut(&[Tr::Batch], &mut Tr, AntichainRef<G::Timestamp>, &mut Vec<(D, G::Timestamp, R2)>)+'static,
+    {
+        let mut trace = self.trace.clone();
+
+        self.stream.unary_frontier(Pipeline, name, move |capability, _info| {
+
+            // Capabilities for the input frontier as of the previous call to `logic`.
+            let mut capabilities = CapabilitySet::from_elem(capability);
+            let mut frontier = Antichain::from_elem(<G::Timestamp as Timestamp>::minimum());
+
+            let mut batches = Vec::new();
+            let mut updates = Vec::new();
+
+            move |input, output| {
+
+                input.for_each(|_cap, data| {
+                    batches.extend(data.iter().cloned());
+                });
+
+                if !batches.is_empty() || frontier.elements() != &input.frontier().frontier()[..] {
+
+                    logic(&batches[..], &mut trace, input.frontier().frontier(), &mut updates);
+                    batches.clear();
+
+                    for (data, time, diff) in updates.drain(..) {
+                        output.session(&capabilities.delayed(&time)).give((data, time, diff));
+                    }
+
+                    frontier = input.frontier().frontier().to_owned();
+                    capabilities.downgrade(&frontier.borrow()[..]);
+                    trace.set_logical_compaction(frontier.borrow());
+                    trace.set_physical_compaction(frontier.borrow());
+                }
+            }
+        })
+        .as_collection()
+    }
+
     /// Report values associated with keys at certain times.
     ///
     /// This method consumes a stream of (key, time) queries and reports the corresponding stream of
@@ -0,0 +1,91 @@
+//! A registry of arrangements, published under names.
+//!
+//! Long-running computations often need to attach new consumers to existing arrangements, for
+//! example a new query dataflow that reads from a maintained index. A `Catalog` holds trace
+//! handles by name, from which such consumers can acquire their own handles or import the
+//! arrangements into new dataflows, starting from the current state of the trace and continuing
+//! with its subsequent batches.
+//!
+//! Catalogs are worker-local, as are the traces they hold; each worker should publish its handle
+//! under the same name. Each published handle holds back compaction of its trace, at the frontiers
+//! the handle was given, until it is removed from the catalog.
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use timely::dataflow::Scope;
+use timely::progress::Timestamp;
+
+use lattice::Lattice;
+use trace::TraceReader;
+
+use super::{TraceAgent, Arranged};
+
+/// Trace handles published under names.
+pub struct Catalog {
+    traces: HashMap<String, Box<dyn Any>>,
+}
+
+impl Catalog {
+
+    /// Allocates a new empty catalog.
+    pub fn new() -> Self {
+        Catalog { traces: HashMap::new() }
+    }
+
+    /// Publishes `trace` under `name`, replacing and returning any handle of the same type.
+    pub fn publish<Tr>(&mut self, name: &str, trace: TraceAgent<Tr>) -> Option<TraceAgent<Tr>>
+    where
+        Tr: TraceReader+'static,
+        Tr::Time: Lattice+Ord+Clone+'static,
+    {
+        self.traces
+            .insert(name.to_owned(), Box::new(trace))
+            .and_then(|prior| prior.downcast().ok())
+            .map(|prior| *prior)
+    }
+
+    /// A new handle to the trace published under `name`, if it exists and has type `Tr`.
+    pub fn get<Tr>(&self, name: &str) -> Option<TraceAgent<Tr>>
+    where
+        Tr: TraceReader+'static,
+        Tr::Time: Lattice+Ord+Clone+'static,
+    {
+        self.traces
+            .get(name)
+            .and_then(|trace| trace.downcast_ref::<TraceAgent<Tr>>())
+            .cloned()
+    }
+
+    /// Imports the trace published under `name` into `scope`, if it exists and has type `Tr`.
+    ///
+    /// The imported arrangement first presents the batches currently in the trace, and then those
+    /// subsequently added to it, as for `TraceAgent::import`.
+    pub fn import<G, Tr>(&self, scope: &G, name: &str) -> Option<Arranged<G, TraceAgent<Tr>>>
+    where
+        G: Scope<Timestamp=Tr::Time>,
+        Tr: TraceReader+'static,
+        Tr::Time: Timestamp+Lattice,
+    {
+        self.get::<Tr>(name).map(|mut trace| trace.import_named(scope, name))
+    }
+
+    /// Removes the handle published under `name`, returning true if there was one.
+    ///
+    /// Removing a handle releases its hold on the trace, but does not affect handles already
+    /// acquired from the catalog.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.traces.remove(name).is_some()
+    }
+
+    /// The names under which traces are published, in no particular order.
+    pub fn names(&self) -> Vec<&str> {
+        self.traces.keys().map(|name| &name[..]).collect()
+    }
+}
+
+impl Default for Catalog {
+    fn default() -> Self {
+        Catalog::new()
+    }
+}
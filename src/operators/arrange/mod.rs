@@ -68,8 +68,13 @@ pub mod agent;
 pub mod arrangement;
 
 pub mod upsert;
+pub mod shared;
+pub mod catalog;
 
 pub use self::writer::TraceWriter;
 pub use self::agent::{TraceAgent, ShutdownButton};
+pub use trace::wrappers::rc::CompactionPolicy;
 
-pub use self::arrangement::{Arranged, Arrange, ArrangeByKey, ArrangeBySelf};
\ No newline at end of file
+pub use self::arrangement::{Arranged, Arrange, ArrangeByKey, ArrangeBySelf};
+pub use self::shared::SharedTrace;
+pub use self::catalog::Catalog;
\ No newline at end of file
@@ -0,0 +1,151 @@
+//! Thread-safe read access to the contents of an arrangement.
+//!
+//! Traces are shared among operators in the same worker using reference counting, and
+//! cannot be handed to other threads. A `SharedTrace` is instead a copy of the accumulated
+//! contents of an arrangement, maintained by the worker as batches are produced, which can
+//! be cloned and read from any thread (e.g. the handlers of a network server) without
+//! coordinating with the worker loop.
+//!
+//! Each read reports the frontier at which it is consistent: the contents reflect exactly
+//! those updates at times not greater or equal to any element of the frontier. As with
+//! the arrangement itself, each worker maintains only the keys it is responsible for.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use timely::dataflow::Scope;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::Operator;
+use timely::progress::{Antichain, Timestamp};
+
+use ::difference::Semigroup;
+use lattice::Lattice;
+use trace::{TraceReader, BatchReader, Cursor};
+
+use super::Arranged;
+
+/// Accumulated contents of an arrangement, readable from any thread.
+///
+/// Handles are cheap to clone, and all clones observe the same contents.
+pub struct SharedTrace<K, V, T, R> {
+    inner: Arc<RwLock<Contents<K, V, T, R>>>,
+}
+
+struct Contents<K, V, T, R> {
+    frontier: Antichain<T>,
+    updates: BTreeMap<K, BTreeMap<V, R>>,
+}
+
+impl<K, V, T, R> Clone for SharedTrace<K, V, T, R> {
+    fn clone(&self) -> Self {
+        SharedTrace { inner: self.inner.clone() }
+    }
+}
+
+impl<K: Ord+Clone, V: Ord+Clone, T: Timestamp+Lattice, R: Semigroup> SharedTrace<K, V, T, R> {
+
+    fn new() -> Self {
+        let contents = Contents {
+            frontier: Antichain::from_elem(<T as Timestamp>::minimum()),
+            updates: BTreeMap::new(),
+        };
+        SharedTrace { inner: Arc::new(RwLock::new(contents)) }
+    }
+
+    /// The frontier through which the contents are complete.
+    ///
+    /// An empty frontier indicates that the arrangement will not change further.
+    pub fn frontier(&self) -> Antichain<T> {
+        self.inner.read().expect("SharedTrace lock poisoned").frontier.clone()
+    }
+
+    /// The accumulated values associated with `key`, and the frontier at which they are consistent.
+    pub fn get(&self, key: &K) -> (Antichain<T>, Vec<(V, R)>) {
+        let contents = self.inner.read().expect("SharedTrace lock poisoned");
+        let vals = contents.updates
+            .get(key)
+            .map(|vals| vals.iter().map(|(v, r)| (v.clone(), r.clone())).collect())
+            .unwrap_or_else(Vec::new);
+        (contents.frontier.clone(), vals)
+    }
+
+    /// The accumulated contents, in key and value order, and the frontier at which they are consistent.
+    pub fn snapshot(&self) -> (Antichain<T>, Vec<((K, V), R)>) {
+        let contents = self.inner.read().expect("SharedTrace lock poisoned");
+        let mut result = Vec::new();
+        for (key, vals) in contents.updates.iter() {
+            for (val, diff) in vals.iter() {
+                result.push(((key.clone(), val.clone()), diff.clone()));
+            }
+        }
+        (contents.frontier.clone(), result)
+    }
+
+    /// Incorporates the updates of `batch`, after which the contents are complete through its upper frontier.
+    fn apply<B>(&self, batch: &B)
+    where
+        B: BatchReader<Key=K, Val=V, Time=T, R=R>,
+    {
+        let mut contents = self.inner.write().expect("SharedTrace lock poisoned");
+        let mut cursor = batch.cursor();
+        while let Some(key) = cursor.get_key(batch) {
+            while let Some(val) = cursor.get_val(batch) {
+                let mut delta: Option<R> = None;
+                cursor.map_times(batch, |_time, diff| {
+                    match delta {
+                        Some(ref mut delta) => delta.plus_equals(diff),
+                        None => delta = Some(diff.clone()),
+                    }
+                });
+                if let Some(delta) = delta {
+                    let empty = {
+                        let vals = contents.updates.entry(key.clone()).or_insert_with(BTreeMap::new);
+                        let zero = match vals.get_mut(val) {
+                            Some(diff) => { diff.plus_equals(&delta); diff.is_zero() },
+                            None => { let zero = delta.is_zero(); if !zero { vals.insert(val.clone(), delta); } zero },
+                        };
+                        if zero { vals.remove(val); }
+                        vals.is_empty()
+                    };
+                    if empty { contents.updates.remove(key); }
+                }
+                cursor.step_val(batch);
+            }
+            cursor.step_key(batch);
+        }
+        contents.frontier = batch.upper().clone();
+    }
+
+    fn close(&self) {
+        self.inner.write().expect("SharedTrace lock poisoned").frontier = Antichain::new();
+    }
+}
+
+/// Maintains a `SharedTrace` from the batches of `arranged`.
+///
+/// This is the implementation of `Arranged::share`.
+pub fn share<G, Tr>(arranged: &Arranged<G, Tr>) -> SharedTrace<Tr::Key, Tr::Val, G::Timestamp, Tr::R>
+where
+    G: Scope,
+    G::Timestamp: Lattice+Ord,
+    Tr: TraceReader<Time=G::Timestamp>+Clone,
+    Tr::Key: Ord+Clone,
+    Tr::Val: Ord+Clone,
+    Tr::R: Semigroup,
+{
+    let shared = SharedTrace::new();
+    let result = shared.clone();
+
+    arranged.stream.sink(Pipeline, "ShareTrace", move |input| {
+        input.for_each(|_time, data| {
+            for batch in data.iter() {
+                shared.apply(batch);
+            }
+        });
+        if input.frontier().frontier().is_empty() {
+            shared.close();
+        }
+    });
+
+    result
+}
@@ -10,7 +10,7 @@ use lattice::Lattice;
 use trace::{Trace, Batch, BatchReader};
 use timely::progress::{Antichain, Timestamp};
 
-use trace::wrappers::rc::TraceBox;
+use trace::wrappers::rc::{TraceBox, CompactionPolicy};
 
 use super::TraceAgentQueueWriter;
 use super::TraceReplayInstruction;
@@ -87,7 +87,11 @@ where
 
         // push data to the trace, if it still exists.
         if let Some(trace) = self.trace.upgrade() {
-            trace.borrow_mut().trace.insert(batch);
+            let mut borrow = trace.borrow_mut();
+            borrow.trace.insert(batch);
+            if borrow.policy == CompactionPolicy::Eager {
+                borrow.trace.purge();
+            }
         }
 
     }
@@ -7,6 +7,7 @@
 //! drop out of, e.g. iterative computations.
 
 use timely::dataflow::Scope;
+use timely::dataflow::channels::pact::ParallelizationContract;
 
 use ::{Collection, ExchangeData, Hashable};
 use ::difference::Semigroup;
@@ -112,4 +113,106 @@ where
             })
             .as_collection()
     }
+
+    /// Aggregates the weights of equal records, and reports them in a deterministic order.
+    ///
+    /// All updates are routed to the first worker, and held back until their times are complete.
+    /// The updates at each time are then consolidated and reported sorted by record, in a single
+    /// message, and times are reported in sorted order. For a fixed input the output is the same
+    /// across runs and numbers of workers, which makes it suitable for comparing against expected
+    /// outputs in tests. It is not suitable for large collections, as one worker does all the work.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let x = scope.new_collection_from(1 .. 10u32).1;
+    ///
+    ///         x.concat(&x)
+    ///          .consolidate_sorted()
+    ///          .assert_eq(&x.concat(&x));
+    ///     });
+    /// }
+    /// ```
+    pub fn consolidate_sorted(&self) -> Self {
+        use timely::dataflow::channels::pact::Exchange;
+        self.consolidate_buffered(Exchange::new(|_| 0), "ConsolidateSorted")
+    }
+
+    /// Aggregates the weights of equal records at each time, reporting them once the time is complete.
+    ///
+    /// Updates are partitioned among workers by record, as in `consolidate`, and so the output contains
+    /// at most one update for each record and time. Unlike `consolidate`, the operator does not maintain
+    /// an arrangement of the collection, and only holds updates until their times are complete. This is
+    /// appropriate for the outputs of operators that may produce cancelling updates, such as `join`, on
+    /// their way to a sink that should not observe them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use differential_dataflow::input::Input;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         let x = scope.new_collection_from(1 .. 10u32).1;
+    ///
+    ///         x.negate()
+    ///          .concat(&x)
+    ///          .consolidate_complete()
+    ///          .inspect(|x| panic!("unexpected output: {:?}", x));
+    ///     });
+    /// }
+    /// ```
+    pub fn consolidate_complete(&self) -> Self {
+        use timely::dataflow::channels::pact::Exchange;
+        self.consolidate_buffered(Exchange::new(|update: &(D,G::Timestamp,R)| update.0.hashed().into()), "ConsolidateComplete")
+    }
+
+    /// Holds updates until their times are complete, and then reports them consolidated and sorted.
+    ///
+    /// Times are reported in sorted order, each in a single message.
+    fn consolidate_buffered<P>(&self, pact: P, name: &str) -> Self
+    where
+        P: ParallelizationContract<G::Timestamp, (D, G::Timestamp, R)>,
+    {
+
+        use timely::dataflow::operators::Operator;
+        use collection::AsCollection;
+        use operators::stash::Stash;
+
+        self.inner
+            .unary_frontier(pact, name, |_cap, _info| {
+
+                let mut vector = Vec::new();
+                let mut stash = Stash::new();
+
+                move |input, output| {
+
+                    input.for_each(|cap, data| {
+                        data.swap(&mut vector);
+                        for (data, time, diff) in vector.drain(..) {
+                            stash.push(&cap, time.clone(), (data, time, diff));
+                        }
+                    });
+
+                    // Report the updates at each complete time, in order of time.
+                    for (cap, mut updates) in stash.drain_complete(input.frontier().frontier()) {
+                        crate::consolidation::consolidate_updates(&mut updates);
+                        output.session(&cap).give_vec(&mut updates);
+                    }
+                }
+            })
+            .as_collection()
+    }
 }
@@ -405,6 +405,9 @@ impl<G, T1> JoinCore<G, T1::Key, T1::Val, T1::R> for Arranged<G,T1>
             let activations = self.stream.scope().activations().clone();
             let activator = Activator::new(&info.address[..], activations);
 
+            // The amount of output to produce before yielding, should work remain.
+            let fuel_limit = self.stream.scope().config().get::<usize>("differential/join_fuel").cloned().unwrap_or(1_000_000);
+
             // Our initial invariants are that for each trace, physical compaction is less or equal the trace's upper bound.
             // These invariants ensure that we can reference observed batch frontiers from `_start_upper` onward, as long as
             // we maintain our physical compaction capabilities appropriately. These assertions are tested as we load up the
@@ -560,7 +563,7 @@ impl<G, T1> JoinCore<G, T1::Key, T1::Val, T1::R> for Arranged<G,T1>
                 // input must scan all batches from the other input).
 
                 // Perform some amount of outstanding work.
-                let mut fuel = 1_000_000;
+                let mut fuel = fuel_limit;
                 while !todo1.is_empty() && fuel > 0 {
                     todo1.front_mut().unwrap().work(
                         output,
@@ -571,7 +574,7 @@ impl<G, T1> JoinCore<G, T1::Key, T1::Val, T1::R> for Arranged<G,T1>
                 }
 
                 // Perform some amount of outstanding work.
-                let mut fuel = 1_000_000;
+                let mut fuel = fuel_limit;
                 while !todo2.is_empty() && fuel > 0 {
                     todo2.front_mut().unwrap().work(
                         output,
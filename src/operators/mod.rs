@@ -17,6 +17,9 @@ pub mod iterate;
 pub mod join;
 pub mod count;
 pub mod threshold;
+pub mod reclock;
+
+pub(crate) mod stash;
 
 use ::difference::Semigroup;
 use lattice::Lattice;
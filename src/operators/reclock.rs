@@ -0,0 +1,119 @@
+//! Aligns the updates of a collection to the ticks of a clock collection.
+//!
+//! Sources often have their own notion of time, for example event times or source-specific
+//! watermarks, which do not correspond to the times at which the dataflow should react. The
+//! `reclock` operator holds back updates and releases them at the times of a second, "clock"
+//! collection, so that a source's updates are only reflected at times the clock designates.
+
+use std::collections::BTreeSet;
+
+use timely::order::TotalOrder;
+use timely::dataflow::Scope;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::{Map, Broadcast};
+use timely::dataflow::operators::generic::Operator;
+
+use ::{Collection, AsCollection, Data};
+use ::difference::Semigroup;
+use lattice::Lattice;
+use operators::stash::Stash;
+
+impl<G, D, R> Collection<G, D, R>
+where
+    G: Scope,
+    G::Timestamp: Lattice+TotalOrder,
+    D: Data,
+    R: Semigroup,
+{
+    /// Delays each update to the first time at or after it at which `clock` has an update.
+    ///
+    /// Any update to `clock` marks its time as a tick, regardless of its data or difference, and
+    /// ticks are shared by all workers. An update is released once its tick is known, and no
+    /// earlier tick can still arrive. Updates at times after the final tick are never released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate timely;
+    /// extern crate differential_dataflow;
+    ///
+    /// use timely::dataflow::operators::Map;
+    /// use differential_dataflow::AsCollection;
+    /// use differential_dataflow::input::Input;
+    ///
+    /// fn main() {
+    ///     ::timely::example(|scope| {
+    ///
+    ///         // records 0 .. 4 at times equal to their values, and ticks at times 2 and 4.
+    ///         let data = scope.new_collection_from(0 .. 4).1
+    ///                         .inner.map(|(x, _t, r)| (x, x, r)).as_collection();
+    ///         let clock = scope.new_collection_from(vec![2, 4]).1
+    ///                          .inner.map(|(x, _t, r)| (x, x, r)).as_collection();
+    ///
+    ///         data.reclock(&clock)
+    ///             .assert_eq(&data.delay(|t| if *t <= 2 { 2 } else { 4 }));
+    ///     });
+    /// }
+    /// ```
+    pub fn reclock<D2, R2>(&self, clock: &Collection<G, D2, R2>) -> Collection<G, D, R>
+    where
+        D2: Data,
+        R2: Semigroup,
+    {
+        let ticks = clock.inner.map(|(_data, time, _diff)| time).broadcast();
+
+        self.inner
+            .binary_frontier(&ticks, Pipeline, Pipeline, "Reclock", |_cap, _info| {
+
+                let mut vector = Vec::new();
+                let mut times = Vec::new();
+                let mut stash = Stash::new();
+                let mut ticks = BTreeSet::<G::Timestamp>::new();
+                let mut ready = Vec::new();
+
+                move |input1, input2, output| {
+
+                    input1.for_each(|cap, data| {
+                        data.swap(&mut vector);
+                        for (data, time, diff) in vector.drain(..) {
+                            stash.push(&cap, time.clone(), (data, time, diff));
+                        }
+                    });
+
+                    input2.for_each(|_cap, data| {
+                        data.swap(&mut times);
+                        ticks.extend(times.drain(..));
+                    });
+
+                    // A stashed time is ready once its first tick is known, and no earlier tick can arrive.
+                    for time in stash.times() {
+                        if let Some(tick) = ticks.range(time.clone() ..).next() {
+                            if !input2.frontier().less_than(tick) {
+                                ready.push((time.clone(), tick.clone()));
+                            }
+                        }
+                    }
+                    for (time, tick) in ready.drain(..) {
+                        let (cap, mut updates) = stash.remove(&time).expect("time absent from stash");
+                        for update in updates.iter_mut() {
+                            update.1 = tick.clone();
+                        }
+                        output.session(&cap.delayed(&tick)).give_vec(&mut updates);
+                    }
+
+                    // Without further ticks, the remaining updates will never be released.
+                    if input2.frontier().frontier().is_empty() {
+                        stash.clear();
+                    }
+
+                    // Ticks before all stashed and future updates will not be used again.
+                    let lower = stash.times().next().cloned().into_iter().chain(input1.frontier().frontier().iter().cloned()).min();
+                    match lower {
+                        Some(lower) => { ticks = ticks.split_off(&lower); },
+                        None => { ticks.clear(); },
+                    }
+                }
+            })
+            .as_collection()
+    }
+}
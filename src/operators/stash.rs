@@ -0,0 +1,52 @@
+//! Updates held back by an operator until their times are complete.
+
+use std::collections::BTreeMap;
+
+use timely::progress::Timestamp;
+use timely::progress::frontier::AntichainRef;
+use timely::dataflow::operators::{Capability, InputCapability};
+
+/// Data grouped by time, each time with a capability for it.
+///
+/// Operators that must see all data at a time before acting on it stash the data as it arrives,
+/// and drain the times that their input frontier has passed.
+pub(crate) struct Stash<T: Timestamp, D> {
+    times: BTreeMap<T, (Capability<T>, Vec<D>)>,
+    ready: Vec<T>,
+}
+
+impl<T: Timestamp, D> Stash<T, D> {
+    /// Allocates a new empty stash.
+    pub(crate) fn new() -> Self {
+        Stash { times: BTreeMap::new(), ready: Vec::new() }
+    }
+    /// Stashes `data` at `time`, retaining a capability for `time` derived from `cap` if needed.
+    pub(crate) fn push(&mut self, cap: &InputCapability<T>, time: T, data: D) {
+        self.times
+            .entry(time.clone())
+            .or_insert_with(|| (cap.delayed(&time), Vec::new()))
+            .1
+            .push(data);
+    }
+    /// The times with stashed data, in order.
+    pub(crate) fn times<'a>(&'a self) -> impl Iterator<Item=&'a T>+'a {
+        self.times.keys()
+    }
+    /// Removes and returns the data stashed at `time`, with its capability.
+    pub(crate) fn remove(&mut self, time: &T) -> Option<(Capability<T>, Vec<D>)> {
+        self.times.remove(time)
+    }
+    /// Removes and returns the data at each time not greater or equal to `frontier`, in order of time.
+    pub(crate) fn drain_complete(&mut self, frontier: AntichainRef<T>) -> Vec<(Capability<T>, Vec<D>)> {
+        self.ready.extend(self.times.keys().filter(|time| !frontier.less_equal(time)).cloned());
+        let mut result = Vec::with_capacity(self.ready.len());
+        for time in self.ready.drain(..) {
+            result.push(self.times.remove(&time).expect("time absent from stash"));
+        }
+        result
+    }
+    /// Discards all stashed data, releasing their capabilities.
+    pub(crate) fn clear(&mut self) {
+        self.times.clear();
+    }
+}
@@ -0,0 +1,357 @@
+//! A reference implementation of differential dataflow semantics.
+//!
+//! The types in this module evaluate collections from scratch, by accumulating all updates at each
+//! time of interest and applying operators to the accumulated contents. This is slow, but it is also
+//! simple enough to be obviously correct, which makes it useful for cross-checking the incremental
+//! operators, and user pipelines built from them, in tests.
+//!
+//! A differential dataflow collection at time `t` should equal the accumulation of its updates at
+//! times less or equal to `t`. A pipeline can be validated by capturing its input and output updates
+//! in `Reference` instances, and comparing the output accumulated at each time with the result of
+//! applying the equivalent `Snapshot` operators to the input accumulated at that time.
+//!
+//! # Examples
+//!
+//! ```
+//! use differential_dataflow::reference::Reference;
+//!
+//! let mut input = Reference::new();
+//! input.update((0, 'a'), 0, 1);
+//! input.update((0, 'b'), 1, 1);
+//! input.update((0, 'a'), 2, -1);
+//!
+//! // Counting the values associated with each key, as `count` would.
+//! let counts = input.at(&1).map(|(key, _val)| key).count();
+//! assert_eq!(counts.updates(), &[((0, 2), 1)]);
+//! ```
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use timely::order::PartialOrder;
+use timely::progress::Timestamp;
+use timely::dataflow::scopes::Child;
+use timely::worker::Worker;
+use timely::communication::allocator::thread::Thread;
+
+use ::{Collection, ExchangeData};
+use ::difference::{Semigroup, Abelian, Multiply};
+use consolidation::consolidate;
+use input::Input;
+use lattice::Lattice;
+
+/// A collection of updates, each at a logical time.
+#[derive(Clone, Debug)]
+pub struct Reference<D, T, R=isize> {
+    updates: Vec<(D, T, R)>,
+}
+
+impl<D: Ord+Clone, T: PartialOrder+Ord+Clone, R: Semigroup> Reference<D, T, R> {
+    /// Allocates a new empty collection.
+    pub fn new() -> Self {
+        Reference { updates: Vec::new() }
+    }
+    /// Records a change to the weight of `data` at `time`.
+    pub fn update(&mut self, data: D, time: T, diff: R) {
+        self.updates.push((data, time, diff));
+    }
+    /// Records a sequence of updates.
+    pub fn extend<I: IntoIterator<Item=(D, T, R)>>(&mut self, updates: I) {
+        self.updates.extend(updates);
+    }
+    /// The updates recorded so far, in the order they were recorded.
+    pub fn updates(&self) -> &[(D, T, R)] {
+        &self.updates[..]
+    }
+    /// The distinct times at which updates were recorded, in sorted order.
+    pub fn times(&self) -> Vec<T> {
+        let mut times: Vec<T> = self.updates.iter().map(|x| x.1.clone()).collect();
+        times.sort();
+        times.dedup();
+        times
+    }
+    /// The contents of the collection at `time`.
+    ///
+    /// This is the accumulation of all updates at times less or equal to `time`.
+    pub fn at(&self, time: &T) -> Snapshot<D, R> {
+        Snapshot::new(
+            self.updates
+                .iter()
+                .filter(|x| x.1.less_equal(time))
+                .map(|x| (x.0.clone(), x.2.clone()))
+                .collect()
+        )
+    }
+}
+
+/// The accumulated contents of a collection at some time.
+///
+/// The updates are consolidated: sorted by record, with distinct records and non-zero weights.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot<D, R=isize> {
+    updates: Vec<(D, R)>,
+}
+
+impl<D: Ord+Clone, R: Semigroup> Snapshot<D, R> {
+    /// Creates a snapshot from unconsolidated updates.
+    pub fn new(mut updates: Vec<(D, R)>) -> Self {
+        consolidate(&mut updates);
+        Snapshot { updates }
+    }
+    /// The consolidated records and their weights.
+    pub fn updates(&self) -> &[(D, R)] {
+        &self.updates[..]
+    }
+    /// True if the snapshot contains no records.
+    pub fn is_empty(&self) -> bool {
+        self.updates.is_empty()
+    }
+    /// The contents of the snapshot with each record transformed by `logic`.
+    pub fn map<D2: Ord+Clone, L: FnMut(D)->D2>(&self, mut logic: L) -> Snapshot<D2, R> {
+        Snapshot::new(self.updates.iter().map(|(d, r)| (logic(d.clone()), r.clone())).collect())
+    }
+    /// The contents of the snapshot with each record replaced by those `logic` produces.
+    pub fn flat_map<I: IntoIterator, L: FnMut(D)->I>(&self, mut logic: L) -> Snapshot<I::Item, R>
+    where I::Item: Ord+Clone {
+        let mut updates = Vec::new();
+        for (data, diff) in self.updates.iter() {
+            updates.extend(logic(data.clone()).into_iter().map(|d| (d, diff.clone())));
+        }
+        Snapshot::new(updates)
+    }
+    /// The records of the snapshot satisfying `logic`.
+    pub fn filter<L: FnMut(&D)->bool>(&self, mut logic: L) -> Snapshot<D, R> {
+        Snapshot::new(self.updates.iter().filter(|x| logic(&x.0)).cloned().collect())
+    }
+    /// The accumulated contents of the two snapshots.
+    pub fn concat(&self, other: &Self) -> Snapshot<D, R> {
+        Snapshot::new(self.updates.iter().chain(other.updates.iter()).cloned().collect())
+    }
+    /// The records of the snapshot, each with a unit count.
+    pub fn distinct(&self) -> Snapshot<D, isize> {
+        self.threshold(|_,_| 1)
+    }
+    /// Each record of the snapshot paired with its weight, as the `count` operator does.
+    pub fn count(&self) -> Snapshot<(D, R), isize> {
+        Snapshot::new(self.updates.iter().map(|(d, r)| ((d.clone(), r.clone()), 1)).collect())
+    }
+    /// The records of the snapshot, with weights determined by `thresh`.
+    pub fn threshold<R2: Semigroup, F: FnMut(&D, &R)->R2>(&self, mut thresh: F) -> Snapshot<D, R2> {
+        Snapshot::new(self.updates.iter().map(|(d, r)| (d.clone(), thresh(d, r))).collect())
+    }
+}
+
+impl<D: Ord+Clone, R: Abelian> Snapshot<D, R> {
+    /// The contents of the snapshot with weights negated.
+    pub fn negate(&self) -> Snapshot<D, R> {
+        Snapshot::new(self.updates.iter().map(|(d, r)| (d.clone(), r.clone().negate())).collect())
+    }
+}
+
+impl<K: Ord+Clone, V: Ord+Clone, R: Semigroup> Snapshot<(K, V), R> {
+    /// Matches pairs of records with equal keys, multiplying their weights.
+    pub fn join<V2, R2>(&self, other: &Snapshot<(K, V2), R2>) -> Snapshot<(K, (V, V2)), <R as Multiply<R2>>::Output>
+    where
+        V2: Ord+Clone,
+        R2: Semigroup,
+        R: Multiply<R2>,
+        <R as Multiply<R2>>::Output: Semigroup,
+    {
+        let mut updates = Vec::new();
+        for ((key1, val1), diff1) in self.updates.iter() {
+            for ((key2, val2), diff2) in other.updates.iter() {
+                if key1 == key2 {
+                    updates.push(((key1.clone(), (val1.clone(), val2.clone())), diff1.clone().multiply(diff2)));
+                }
+            }
+        }
+        Snapshot::new(updates)
+    }
+    /// Applies `logic` to the values associated with each key, as the `reduce` operator does.
+    ///
+    /// As with `reduce`, the logic is only invoked for keys with at least one value, and is provided
+    /// the values in sorted order.
+    pub fn reduce<V2, R2, L>(&self, mut logic: L) -> Snapshot<(K, V2), R2>
+    where
+        V2: Ord+Clone,
+        R2: Semigroup,
+        L: FnMut(&K, &[(&V, R)], &mut Vec<(V2, R2)>),
+    {
+        let mut updates = Vec::new();
+        let mut input = Vec::new();
+        let mut output = Vec::new();
+        let mut index = 0;
+        while index < self.updates.len() {
+            let key = &(self.updates[index].0).0;
+            while index < self.updates.len() && &(self.updates[index].0).0 == key {
+                input.push((&(self.updates[index].0).1, self.updates[index].1.clone()));
+                index += 1;
+            }
+            logic(key, &input[..], &mut output);
+            updates.extend(output.drain(..).map(|(v, r)| ((key.clone(), v), r)));
+            input.clear();
+        }
+        Snapshot::new(updates)
+    }
+}
+
+/// A source of pseudo-random update sequences.
+///
+/// The generator is a small xorshift generator, so that sequences are reproducible from their seed
+/// without external dependencies. It is not suitable for anything other than testing.
+pub struct Generator {
+    state: u64,
+}
+
+impl Generator {
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        // The xorshift state must be non-zero, and so the one seed that would zero it is mapped elsewhere.
+        let state = seed ^ 0x9E37_79B9_7F4A_7C15;
+        Generator { state: if state == 0 { 0x9E37_79B9_7F4A_7C15 } else { state } }
+    }
+    /// Returns the next pseudo-random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+    /// Returns a pseudo-random number less than `bound`.
+    ///
+    /// The `bound` must be positive.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+        self.next_u64() % bound
+    }
+    /// Produces `count` updates to records drawn from `0 .. records`, over rounds `0 .. rounds`.
+    ///
+    /// Updates are ordered by round. Each update either inserts a record or retracts a record that
+    /// is present at that point in the sequence, so that no record ever has a negative count.
+    /// Unless `count` is zero, both `records` and `rounds` must be positive.
+    pub fn updates(&mut self, count: usize, records: u64, rounds: usize) -> Vec<(u64, usize, isize)> {
+        assert!(count == 0 || (records > 0 && rounds > 0), "records and rounds must be positive");
+        let mut times: Vec<usize> = (0 .. count).map(|_| self.below(rounds as u64) as usize).collect();
+        times.sort();
+        let mut present = Vec::new();
+        let mut updates = Vec::with_capacity(count);
+        for time in times {
+            if present.len() > 0 && self.below(2) == 0 {
+                let index = self.below(present.len() as u64) as usize;
+                updates.push((present.swap_remove(index), time, -1));
+            }
+            else {
+                let record = self.below(records);
+                present.push(record);
+                updates.push((record, time, 1));
+            }
+        }
+        updates
+    }
+}
+
+/// Compares an incremental computation against its reference evaluation.
+///
+/// The `updates` are introduced in order of their times to a dataflow constructed by `dataflow`, with
+/// the input advanced to the meet of the times of the updates yet to come and the dataflow allowed to
+/// catch up before each new time. For totally ordered times this introduces the updates round by round.
+/// The output is then compared with `reference` applied to the input, at every join of input times and
+/// at every time at which the output changes. The method panics at the first time at which they differ.
+/// The `updates` may be supplied in any order.
+///
+/// # Examples
+///
+/// ```
+/// use differential_dataflow::operators::Count;
+/// use differential_dataflow::reference::{compare, Generator};
+///
+/// let updates = Generator::new(0).updates(100, 10, 5);
+/// compare(
+///     updates,
+///     |input| input.map(|x| (x % 3, x)).count(),
+///     |input| input.map(|x| (x % 3, x)).count(),
+/// );
+/// ```
+pub fn compare<D, D2, T, R, R2, F, L>(mut updates: Vec<(D, T, R)>, dataflow: F, reference: L)
+where
+    D: ExchangeData,
+    D2: ExchangeData,
+    T: Lattice+Timestamp,
+    R: ExchangeData+Abelian,
+    R2: Semigroup,
+    F: for<'a> FnOnce(&Collection<Child<'a, Worker<Thread>, T>, D, R>) -> Collection<Child<'a, Worker<Thread>, T>, D2, R2>+Send+Sync+'static,
+    L: Fn(&Snapshot<D, R>) -> Snapshot<D2, R2>,
+{
+    updates.sort_by(|x, y| x.1.cmp(&y.1));
+
+    // The input is advanced to the meet of the times of each suffix of the updates.
+    let mut frontiers: Vec<T> = updates.iter().map(|x| x.1.clone()).collect();
+    for index in (1 .. frontiers.len()).rev() {
+        let meet = frontiers[index - 1].meet(&frontiers[index]);
+        frontiers[index - 1] = meet;
+    }
+
+    let mut input = Reference::new();
+    input.extend(updates.iter().cloned());
+
+    let results = ::timely::execute_directly(move |worker| {
+
+        let (mut handle, probe, results) = worker.dataflow::<T,_,_>(move |scope| {
+            let (handle, collection) = scope.new_collection();
+            let results = Rc::new(RefCell::new(Vec::new()));
+            let captured = results.clone();
+            let probe =
+            dataflow(&collection)
+                .inspect(move |x| captured.borrow_mut().push(x.clone()))
+                .probe();
+            (handle, probe, results)
+        });
+
+        for ((data, time, diff), frontier) in updates.into_iter().zip(frontiers.into_iter()) {
+            if handle.time() != &frontier {
+                handle.advance_to(frontier);
+                handle.flush();
+                while probe.less_than(handle.time()) {
+                    worker.step();
+                }
+            }
+            handle.update_at(data, time, diff);
+        }
+        handle.close();
+        while !probe.done() {
+            worker.step();
+        }
+
+        let results = results.borrow().clone();
+        results
+    });
+
+    let mut output = Reference::new();
+    output.extend(results);
+
+    // Collections only change at joins of their update times.
+    let mut times = input.times();
+    loop {
+        let mut joins = times.clone();
+        for time1 in times.iter() {
+            for time2 in times.iter() {
+                joins.push(time1.join(time2));
+            }
+        }
+        joins.sort();
+        joins.dedup();
+        if joins.len() == times.len() { break; }
+        times = joins;
+    }
+    times.extend(output.times());
+    times.sort();
+    times.dedup();
+
+    for time in times {
+        let expected = reference(&input.at(&time));
+        let observed = output.at(&time);
+        if expected != observed {
+            panic!("At time {:?}: expected {:?}, observed {:?}", time, expected, observed);
+        }
+    }
+}
@@ -11,6 +11,24 @@ pub mod cursor_list;
 
 pub use self::cursor_list::CursorList;
 
+use timely::order::PartialOrder;
+
+use ::difference::Semigroup;
+use ::consolidation::consolidate;
+
+/// Statistics about the updates associated with a key.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct KeyStats {
+    /// The number of values associated with the key.
+    pub vals: usize,
+    /// The number of distinct times at which the key has updates.
+    pub times: usize,
+    /// The number of `(val, time, diff)` updates associated with the key.
+    pub updates: usize,
+    /// The number of values whose updates accumulate to a non-zero difference.
+    pub accumulated: usize,
+}
+
 /// A cursor for navigating ordered `(key, val, time, diff)` updates.
 pub trait Cursor {
 
@@ -51,6 +69,10 @@ pub trait Cursor {
 
     /// Applies `logic` to each pair of time and difference. Intended for mutation of the
     /// closure's scope.
+    ///
+    /// Cursors over a single batch present times in sorted order. Cursors over several batches,
+    /// such as `CursorList`, present the times of each batch in turn, and for trace cursors these
+    /// are ordered from the oldest batch to the most recent.
     fn map_times<L: FnMut(&Self::Time, &Self::R)>(&mut self, storage: &Self::Storage, logic: L);
 
     /// Advances the cursor to the next key.
@@ -68,6 +90,135 @@ pub trait Cursor {
     /// Rewinds the cursor to the first value for current key.
     fn rewind_vals(&mut self, storage: &Self::Storage);
 
+    /// Rewinds the cursor and applies `logic` to each key, in order.
+    ///
+    /// When `logic` is called the cursor is positioned at the first value of the key, and `logic`
+    /// may navigate the values of the key and their updates. It should not move to other keys.
+    fn for_each_key<L: FnMut(&Self::Key, &mut Self, &Self::Storage)>(&mut self, storage: &Self::Storage, mut logic: L) {
+        self.rewind_keys(storage);
+        while let Some(key) = self.get_key(storage) {
+            self.rewind_vals(storage);
+            logic(key, self, storage);
+            self.step_key(storage);
+        }
+    }
+
+    /// Rewinds the cursor and outputs its keys, in order, to a Vec.
+    fn keys(&mut self, storage: &Self::Storage) -> Vec<Self::Key>
+    where
+        Self::Key: Clone,
+    {
+        let mut out = Vec::new();
+        self.for_each_key(storage, |key, _, _| out.push(key.clone()));
+        out
+    }
+
+    /// Reports statistics about the updates associated with `key`.
+    ///
+    /// The statistics are all zero if the key is not present.
+    fn key_stats(&mut self, storage: &Self::Storage, key: &Self::Key) -> KeyStats
+    where
+        Self::Key: PartialEq,
+        Self::Time: Ord+Clone,
+        Self::R: Semigroup,
+    {
+        self.rewind_keys(storage);
+        self.seek_key(storage, key);
+        if self.get_key(storage) == Some(key) {
+            self.rewind_vals(storage);
+            current_key_stats(self, storage)
+        }
+        else {
+            KeyStats::default()
+        }
+    }
+
+    /// Rewinds the cursor and reports the `count` keys with the most updates, and their statistics.
+    ///
+    /// The keys are reported in order of decreasing numbers of updates.
+    fn heaviest_keys(&mut self, storage: &Self::Storage, count: usize) -> Vec<(Self::Key, KeyStats)>
+    where
+        Self::Key: Clone,
+        Self::Time: Ord+Clone,
+        Self::R: Semigroup,
+    {
+        let mut out = Vec::new();
+        self.for_each_key(storage, |key, cursor, storage| {
+            out.push((key.clone(), current_key_stats(cursor, storage)));
+        });
+        out.sort_by(|x, y| y.1.updates.cmp(&x.1.updates));
+        out.truncate(count);
+        out
+    }
+
+    /// Accumulates the values of the current key at `time`, into `target`.
+    ///
+    /// Each value whose updates at times less or equal to `time` accumulate to a non-zero difference
+    /// is pushed to `target` with its accumulation, in order of value. Values are not cloned, but are
+    /// referenced in `storage`, which avoids allocation for collections of large values.
+    fn accumulate_vals<'a>(&mut self, storage: &'a Self::Storage, time: &Self::Time, target: &mut Vec<(&'a Self::Val, Self::R)>)
+    where
+        Self::Time: PartialOrder,
+        Self::R: Semigroup,
+    {
+        self.rewind_vals(storage);
+        while let Some(val) = self.get_val(storage) {
+            let mut sum: Option<Self::R> = None;
+            self.map_times(storage, |t, diff| {
+                if t.less_equal(time) {
+                    match sum.as_mut() {
+                        Some(total) => total.plus_equals(diff),
+                        None => sum = Some(diff.clone()),
+                    }
+                }
+            });
+            if let Some(total) = sum {
+                if !total.is_zero() {
+                    target.push((val, total));
+                }
+            }
+            self.step_val(storage);
+        }
+    }
+
+    /// Reports the changes to the values of `key` at times in the half-open interval `[lower, upper)`.
+    ///
+    /// A time is in the interval if it is greater or equal to `lower` and not greater or equal to
+    /// `upper`. Changes are grouped by time, and the groups are reported in order of time. Within
+    /// each group, the changes are consolidated and ordered by value.
+    fn updates_between(&mut self, storage: &Self::Storage, key: &Self::Key, lower: &Self::Time, upper: &Self::Time) -> Vec<(Self::Time, Vec<(Self::Val, Self::R)>)>
+    where
+        Self::Key: PartialEq,
+        Self::Val: Ord+Clone,
+        Self::Time: PartialOrder+Ord+Clone,
+        Self::R: Semigroup,
+    {
+        let mut updates = Vec::new();
+        self.rewind_keys(storage);
+        self.seek_key(storage, key);
+        if self.get_key(storage) == Some(key) {
+            self.rewind_vals(storage);
+            while let Some(val) = self.get_val(storage) {
+                self.map_times(storage, |time, diff| {
+                    if lower.less_equal(time) && !upper.less_equal(time) {
+                        updates.push(((time.clone(), val.clone()), diff.clone()));
+                    }
+                });
+                self.step_val(storage);
+            }
+        }
+        consolidate(&mut updates);
+
+        let mut out: Vec<(Self::Time, Vec<(Self::Val, Self::R)>)> = Vec::new();
+        for ((time, val), diff) in updates {
+            if out.last().map(|x| x.0 != time).unwrap_or(true) {
+                out.push((time, Vec::new()));
+            }
+            out.last_mut().unwrap().1.push((val, diff));
+        }
+        out
+    }
+
     /// Rewinds the cursor and outputs its contents to a Vec
     fn to_vec(&mut self, storage: &Self::Storage) -> Vec<((Self::Key, Self::Val), Vec<(Self::Time, Self::R)>)>
     where
@@ -93,3 +244,33 @@ pub trait Cursor {
         out
     }
 }
+
+/// Reports statistics about the updates of the cursor's current key, from its current value onward.
+fn current_key_stats<C: Cursor+?Sized>(cursor: &mut C, storage: &C::Storage) -> KeyStats
+where
+    C::Time: Ord+Clone,
+    C::R: Semigroup,
+{
+    let mut stats = KeyStats::default();
+    let mut times = Vec::new();
+    while cursor.val_valid(storage) {
+        stats.vals += 1;
+        let mut sum: Option<C::R> = None;
+        cursor.map_times(storage, |time, diff| {
+            stats.updates += 1;
+            times.push(time.clone());
+            match sum.as_mut() {
+                Some(total) => total.plus_equals(diff),
+                None => sum = Some(diff.clone()),
+            }
+        });
+        if sum.map(|total| !total.is_zero()).unwrap_or(false) {
+            stats.accumulated += 1;
+        }
+        cursor.step_val(storage);
+    }
+    times.sort();
+    times.dedup();
+    stats.times = times.len();
+    stats
+}
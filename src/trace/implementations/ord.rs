@@ -26,7 +26,7 @@ use trace::layers::Builder as TrieBuilder;
 use trace::layers::Cursor as TrieCursor;
 use trace::layers::ordered::{OrdOffset, OrderedLayer, OrderedBuilder, OrderedCursor};
 use trace::layers::ordered_leaf::{OrderedLeaf, OrderedLeafBuilder};
-use trace::{Batch, BatchReader, Builder, Merger, Cursor};
+use trace::{Batch, BatchReader, Builder, Merger, Cursor, Statistics};
 use trace::description::Description;
 
 use trace::layers::MergeBuilder;
@@ -50,6 +50,11 @@ pub type OrdKeySpine<K, T, R, O=usize> = Spine<Rc<OrdKeyBatch<K, T, R, O>>>;
 pub type OrdKeySpineAbom<K, T, R, O=usize> = Spine<Rc<Abomonated<OrdKeyBatch<K, T, R, O>, Vec<u8>>>>;
 
 /// A trace implementation backed by columnar storage.
+///
+/// Keys and values are stored in `TimelyStack` containers, which copy the contents of variable-length
+/// types such as `Vec<u8>` and `String` into large regions shared by each batch, rather than into
+/// individual allocations. This makes it a good choice for arrangements of serialized payloads, whose
+/// values are read through cursors by reference and are not cloned.
 pub type ColValSpine<K, V, T, R, O=usize> = Spine<Rc<OrdValBatch<K, V, T, R, O, TimelyStack<K>, TimelyStack<V>>>>;
 /// A trace implementation backed by columnar storage.
 pub type ColKeySpine<K, T, R, O=usize> = Spine<Rc<OrdKeyBatch<K,  T, R, O, TimelyStack<K>>>>;
@@ -122,6 +127,25 @@ where
     fn cursor(&self) -> Self::Cursor { OrdValCursor { cursor: self.layer.cursor(), phantom: std::marker::PhantomData } }
     fn len(&self) -> usize { <OrderedLayer<K, OrderedLayer<V, OrderedLeaf<T, R>, O, CV>, O, CK> as Trie>::tuples(&self.layer) }
     fn description(&self) -> &Description<T> { &self.desc }
+    /// Reads the counts from the layer lengths, and the largest number of updates for a key from the offsets.
+    fn statistics(&self) -> Statistics {
+        let vals = &self.layer.vals;
+        let mut max_key_updates = 0;
+        for bounds in self.layer.offs.windows(2) {
+            let lower: usize = bounds[0].try_into().unwrap();
+            let upper: usize = bounds[1].try_into().unwrap();
+            let lower: usize = vals.offs[lower].try_into().unwrap();
+            let upper: usize = vals.offs[upper].try_into().unwrap();
+            max_key_updates = ::std::cmp::max(max_key_updates, upper - lower);
+        }
+        Statistics {
+            batches: if self.is_empty() { 0 } else { 1 },
+            keys: self.layer.keys(),
+            vals: vals.keys(),
+            updates: self.len(),
+            max_key_updates: max_key_updates,
+        }
+    }
 }
 
 impl<K, V, T, R, O, CK, CV> Batch for OrdValBatch<K, V, T, R, O, CK, CV>
@@ -141,6 +165,12 @@ where
     fn begin_merge(&self, other: &Self, compaction_frontier: Option<AntichainRef<T>>) -> Self::Merger {
         OrdValMerger::new(self, other, compaction_frontier)
     }
+    fn shrink_to_fit(&mut self) {
+        self.layer.shrink_to_fit();
+    }
+    fn heap_size<F: FnMut(usize, usize)>(&self, callback: F) {
+        self.layer.heap_size(callback);
+    }
 }
 
 impl<K, V, T, R, O, CK, CV> OrdValBatch<K, V, T, R, O, CK, CV>
@@ -499,6 +529,22 @@ where
     }
     fn len(&self) -> usize { <OrderedLayer<K, OrderedLeaf<T, R>, O, CK> as Trie>::tuples(&self.layer) }
     fn description(&self) -> &Description<T> { &self.desc }
+    /// Reads the counts from the layer lengths, and the largest number of updates for a key from the offsets.
+    fn statistics(&self) -> Statistics {
+        let mut max_key_updates = 0;
+        for bounds in self.layer.offs.windows(2) {
+            let lower: usize = bounds[0].try_into().unwrap();
+            let upper: usize = bounds[1].try_into().unwrap();
+            max_key_updates = ::std::cmp::max(max_key_updates, upper - lower);
+        }
+        Statistics {
+            batches: if self.is_empty() { 0 } else { 1 },
+            keys: self.layer.keys(),
+            vals: self.layer.keys(),
+            updates: self.len(),
+            max_key_updates: max_key_updates,
+        }
+    }
 }
 
 impl<K, T, R, O, CK> Batch for OrdKeyBatch<K, T, R, O, CK>
@@ -516,6 +562,12 @@ where
     fn begin_merge(&self, other: &Self, compaction_frontier: Option<AntichainRef<T>>) -> Self::Merger {
         OrdKeyMerger::new(self, other, compaction_frontier)
     }
+    fn shrink_to_fit(&mut self) {
+        self.layer.shrink_to_fit();
+    }
+    fn heap_size<F: FnMut(usize, usize)>(&self, callback: F) {
+        self.layer.heap_size(callback);
+    }
 }
 
 impl<K, T, R, O, CK> OrdKeyBatch<K, T, R, O, CK>
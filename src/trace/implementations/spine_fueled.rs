@@ -97,6 +97,7 @@ pub struct Spine<B: Batch> where B::Time: Lattice+Ord, B::R: Semigroup {
     upper: Antichain<B::Time>,
     effort: usize,
     activator: Option<timely::scheduling::activate::Activator>,
+    purged: Option<Antichain<B::Time>>,     // Logical frontier of the last purge, if no updates have arrived since.
 }
 
 impl<B> TraceReader for Spine<B>
@@ -277,9 +278,12 @@ where
                 let level = (*effort as usize).next_power_of_two().trailing_zeros() as usize;
                 self.introduce_batch(None, level);
             }
-            // We were not in reduced form, so let's check again in the future.
-            if let Some(activator) = &self.activator {
-                activator.activate();
+            // If we are still not in reduced form, let's check again in the future.
+            // Otherwise, we do not reschedule ourself, so that an idle worker may park.
+            if !self.reduced() {
+                if let Some(activator) = &self.activator {
+                    activator.activate();
+                }
             }
         }
     }
@@ -298,7 +302,13 @@ where
         assert!(batch.lower() != batch.upper());
         assert_eq!(batch.lower(), &self.upper);
 
+        #[cfg(debug_assertions)]
+        validate_batch(&batch);
+
         self.upper.clone_from(batch.upper());
+        if !batch.is_empty() {
+            self.purged = None;
+        }
 
         // TODO: Consolidate or discard empty batches.
         self.pending.push(batch);
@@ -314,6 +324,153 @@ where
             self.insert(batch);
         }
     }
+
+    /// Merges all settled batches into one, compacted to the logical frontier.
+    ///
+    /// Updates that cancel once advanced to the logical frontier are only discarded when they are
+    /// merged into the same batch, which for high-churn workloads can leave many keys and values
+    /// with no net updates spread across batches. This method completes all outstanding merges
+    /// eagerly, so that such keys no longer occupy memory or appear to cursors.
+    ///
+    /// The work is proportional to the size of the trace, and is performed immediately. Batches
+    /// not yet beyond the physical compaction frontier are left in place. If no updates have been
+    /// inserted and the logical frontier has not advanced since the last purge, nothing is done.
+    fn purge(&mut self) {
+        if self.purged.as_ref() == Some(&self.logical_frontier) {
+            return;
+        }
+
+        self.consider_merges();
+
+        // Fold each layer into the accumulated result, oldest batches at the highest layers.
+        let mut merged = None;
+        let mut batches = 0;
+        for index in (0 .. self.merging.len()).rev() {
+            let batch = self.complete_at(index);
+            if batch.is_some() { batches += 1; }
+            self.insert_at(merged, index);
+            self.insert_at(batch, index);
+            merged = self.complete_at(index);
+        }
+
+        // Merging a lone batch performs no work, and so we merge it with an empty batch
+        // to ensure that it is compacted. Merges of several batches compact them already.
+        if batches == 1 {
+            let batch = merged.take().expect("lone batch absent");
+            let upper = batch.upper().clone();
+            let empty = B::empty(upper.clone(), upper, self.logical_frontier.clone());
+            self.insert_at(Some(batch), 0);
+            self.insert_at(Some(empty), 0);
+            merged = self.complete_at(0);
+        }
+
+        if let Some(batch) = merged {
+            let level = batch.len().next_power_of_two().trailing_zeros() as usize;
+            self.insert_at(Some(batch), level);
+        }
+        self.tidy_layers();
+
+        // Batches held back by the physical frontier may still need to be purged.
+        if self.pending.is_empty() {
+            self.purged = Some(self.logical_frontier.clone());
+        }
+    }
+
+    /// Trims the excess capacity of settled batches.
+    ///
+    /// Batches participating in in-progress merges are left alone, as are batches shared with
+    /// other holders (for example, other traces or in-flight streams) where the batch type
+    /// cannot be modified through a shared reference.
+    fn shrink_to_fit(&mut self) {
+        for merge_state in self.merging.iter_mut() {
+            match merge_state {
+                MergeState::Single(Some(batch)) => batch.shrink_to_fit(),
+                MergeState::Double(MergeVariant::Complete(Some((batch, _)))) => batch.shrink_to_fit(),
+                _ => { },
+            }
+        }
+        for batch in self.pending.iter_mut() {
+            batch.shrink_to_fit();
+        }
+        self.merging.shrink_to_fit();
+        self.pending.shrink_to_fit();
+    }
+
+    /// Reports the heap allocations of all batches, as for `map_batches`.
+    ///
+    /// Allocations of in-progress merges are not reported, and batches shared with other traces
+    /// are reported by each trace that holds them.
+    fn heap_size<F: FnMut(usize, usize)>(&self, mut callback: F) {
+        self.map_batches(|batch| batch.heap_size(&mut callback));
+    }
+}
+
+impl<B> Spine<B>
+where
+    B: Batch+Clone+'static,
+    B::Key: Ord+Clone,
+    B::Val: Ord+Clone,
+    B::Time: Lattice+timely::progress::Timestamp+Ord+Clone+Debug,
+    B::R: Semigroup,
+{
+    /// Creates a new trace sharing the batches of this trace.
+    ///
+    /// Batches are immutable and shared, so forking a trace does not copy its updates. Updates
+    /// subsequently inserted into either trace are private to that trace, as are any batches
+    /// produced by merging them. The fork has no logger or activator of its own.
+    pub fn fork(&self) -> Self {
+        let mut fork = Self::with_effort(self.effort, self.operator.clone(), None, None);
+        fork.set_logical_compaction(self.logical_frontier.borrow());
+        fork.set_physical_compaction(self.physical_frontier.borrow());
+        self.map_batches(|batch| fork.insert(batch.clone()));
+        fork
+    }
+}
+
+/// Validates the structure of a batch, panicking if it is malformed.
+///
+/// Keys must be strictly increasing, as must values within each key, and each value must have
+/// at least one update. The updates for each value must have distinct times and non-zero
+/// differences, and unless the batch has been advanced its times must lie within its bounds.
+#[cfg(debug_assertions)]
+fn validate_batch<B>(batch: &B)
+where
+    B: BatchReader,
+    B::Key: Ord,
+    B::Val: Ord,
+    B::Time: Lattice+Ord+Clone,
+    B::R: Semigroup,
+{
+    let description = batch.description();
+    let advanced = !PartialOrder::less_equal(description.since(), description.lower());
+
+    let mut times = Vec::new();
+    let mut cursor = batch.cursor();
+    let mut prev_key: Option<&B::Key> = None;
+    while let Some(key) = cursor.get_key(batch) {
+        assert!(prev_key.map(|prev| prev < key).unwrap_or(true), "batch keys are not strictly increasing");
+        prev_key = Some(key);
+        let mut prev_val: Option<&B::Val> = None;
+        while let Some(val) = cursor.get_val(batch) {
+            assert!(prev_val.map(|prev| prev < val).unwrap_or(true), "batch values are not strictly increasing");
+            prev_val = Some(val);
+            cursor.map_times(batch, |time, diff| {
+                assert!(!diff.is_zero(), "batch contains a zero difference");
+                assert!(description.lower().less_equal(time), "batch contains a time not beyond its lower bound");
+                assert!(advanced || !description.upper().less_equal(time), "batch contains a time beyond its upper bound");
+                times.push(time.clone());
+            });
+            assert!(!times.is_empty(), "batch contains a value without updates");
+            let len = times.len();
+            times.sort();
+            times.dedup();
+            assert_eq!(times.len(), len, "batch contains uncoalesced updates");
+            times.clear();
+            cursor.step_val(batch);
+        }
+        assert!(prev_val.is_some(), "batch contains a key without values");
+        cursor.step_key(batch);
+    }
 }
 
 // Drop implementation allows us to log batch drops, to zero out maintained totals.
@@ -438,6 +595,7 @@ where
             upper: Antichain::from_elem(<B::Time as timely::progress::Timestamp>::minimum()),
             effort,
             activator,
+            purged: None,
         }
     }
 
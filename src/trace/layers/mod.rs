@@ -37,6 +37,14 @@ pub trait Trie  : ::std::marker::Sized {
     /// Returns a cursor over a range of data, commonly used by others to restrict navigation to
     /// sub-collections.
     fn cursor_from(&self, lower: usize, upper: usize) -> Self::Cursor;
+    /// Reports the heap allocations of the collection, as `(length, capacity)` pairs in bytes.
+    ///
+    /// The default implementation reports nothing.
+    fn heap_size<F: FnMut(usize, usize)>(&self, _callback: F) { }
+    /// Releases any excess capacity held by the collection.
+    ///
+    /// The default implementation does nothing.
+    fn shrink_to_fit(&mut self) { }
 
     /// Merges two collections into a third.
     ///
@@ -110,6 +118,10 @@ pub trait Cursor<Storage> {
 }
 
 /// A general-purpose container resembling `Vec<T>`.
+///
+/// Keys and values in a trie are stored in implementors of this trait, and alternate
+/// implementations can place them elsewhere, for example in a region that is accounted
+/// for and released as a unit. `TimelyStack` is an example of the latter.
 pub trait BatchContainer: Default {
     /// The type of contained item.
     type Item;
@@ -125,6 +137,14 @@ pub trait BatchContainer: Default {
     fn reserve(&mut self, additional: usize);
     /// Creates a new container with sufficient capacity.
     fn merge_capacity(cont1: &Self, cont2: &Self) -> Self;
+    /// Reports the heap allocations of the container, as `(length, capacity)` pairs in bytes.
+    ///
+    /// The default implementation reports nothing.
+    fn heap_size<F: FnMut(usize, usize)>(&self, _callback: F) { }
+    /// Releases any excess capacity held by the container.
+    ///
+    /// The default implementation does nothing.
+    fn shrink_to_fit(&mut self) { }
 }
 
 impl<T: Clone> BatchContainer for Vec<T> {
@@ -147,6 +167,13 @@ impl<T: Clone> BatchContainer for Vec<T> {
     fn merge_capacity(cont1: &Self, cont2: &Self) -> Self {
         Vec::with_capacity(cont1.len() + cont2.len())
     }
+    fn heap_size<F: FnMut(usize, usize)>(&self, mut callback: F) {
+        let size_of = std::mem::size_of::<T>();
+        callback(self.len() * size_of, self.capacity() * size_of);
+    }
+    fn shrink_to_fit(&mut self) {
+        Vec::shrink_to_fit(self);
+    }
 }
 
 impl<T: Columnation> BatchContainer for TimelyStack<T> {
@@ -173,6 +200,12 @@ impl<T: Columnation> BatchContainer for TimelyStack<T> {
         new.reserve_regions(std::iter::once(cont1).chain(std::iter::once(cont2)));
         new
     }
+    fn heap_size<F: FnMut(usize, usize)>(&self, callback: F) {
+        self.heap_size(callback);
+    }
+    fn shrink_to_fit(&mut self) {
+        // Regions are allocated in fixed-size chunks, and cannot be trimmed in place.
+    }
 }
 
 
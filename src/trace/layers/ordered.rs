@@ -71,6 +71,17 @@ where
             }
         }
     }
+    fn heap_size<F: FnMut(usize, usize)>(&self, mut callback: F) {
+        let size_of = std::mem::size_of::<O>();
+        callback(self.offs.len() * size_of, self.offs.capacity() * size_of);
+        self.keys.heap_size(&mut callback);
+        self.vals.heap_size(callback);
+    }
+    fn shrink_to_fit(&mut self) {
+        self.offs.shrink_to_fit();
+        self.keys.shrink_to_fit();
+        self.vals.shrink_to_fit();
+    }
 }
 
 /// Assembles a layer of this
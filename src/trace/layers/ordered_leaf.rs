@@ -31,6 +31,12 @@ where
             pos: lower,
         }
     }
+    fn heap_size<F: FnMut(usize, usize)>(&self, callback: F) {
+        self.vals.heap_size(callback);
+    }
+    fn shrink_to_fit(&mut self) {
+        self.vals.shrink_to_fit();
+    }
 }
 
 /// A builder for unordered values.
@@ -16,8 +16,9 @@ pub mod wrappers;
 use timely::communication::message::RefOrMut;
 use timely::progress::{Antichain, frontier::AntichainRef};
 use timely::progress::Timestamp;
+use timely::order::PartialOrder;
 
-// use ::difference::Semigroup;
+use ::difference::Semigroup;
 pub use self::cursor::Cursor;
 pub use self::description::Description;
 
@@ -36,6 +37,60 @@ pub use self::description::Description;
 //
 //  We could just start by cloning things. Worry about wrapping references later on.
 
+/// Summary statistics about the contents of a trace.
+///
+/// These are intended to inform choices about how to use a trace, for example the order in which to
+/// join arrangements, and describe the updates as they are stored rather than their accumulations.
+/// Keys and values are counted once for each batch that contains them, so that the statistics of a
+/// trace are the sums of the statistics of its batches.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Statistics {
+    /// The number of non-empty batches in the trace.
+    pub batches: usize,
+    /// The number of keys with updates, summed over batches.
+    pub keys: usize,
+    /// The number of `(key, val)` pairs with updates, summed over batches.
+    pub vals: usize,
+    /// The number of `(key, val, time, diff)` updates.
+    pub updates: usize,
+    /// The largest number of updates associated with any one key in any one batch.
+    pub max_key_updates: usize,
+}
+
+impl Statistics {
+    /// Statistics describing the updates of a single batch.
+    ///
+    /// The statistics are computed by reading the entire batch.
+    pub fn from_batch<B: BatchReader>(batch: &B) -> Self {
+        let mut stats = Statistics::default();
+        if !batch.is_empty() { stats.batches = 1; }
+        let mut cursor = batch.cursor();
+        while cursor.key_valid(batch) {
+            let mut updates = 0;
+            while cursor.val_valid(batch) {
+                stats.vals += 1;
+                cursor.map_times(batch, |_, _| updates += 1);
+                cursor.step_val(batch);
+            }
+            stats.keys += 1;
+            stats.updates += updates;
+            if stats.max_key_updates < updates {
+                stats.max_key_updates = updates;
+            }
+            cursor.step_key(batch);
+        }
+        stats
+    }
+    /// The ratio of the largest number of updates for a key to the average number for a key.
+    ///
+    /// A value near one indicates that updates are evenly spread across keys, whereas large values
+    /// indicate that some keys are much heavier than others. The skew of an empty trace is one.
+    pub fn skew(&self) -> f64 {
+        if self.updates == 0 { 1.0 }
+        else { (self.max_key_updates * self.keys) as f64 / self.updates as f64 }
+    }
+}
+
 /// A trace whose contents may be read.
 ///
 /// This is a restricted interface to the more general `Trace` trait, which extends this trait with further methods
@@ -157,6 +212,95 @@ pub trait TraceReader {
     /// cursor methods, as they (by default) just move through batches accumulating cursors into a cursor list.
     fn map_batches<F: FnMut(&Self::Batch)>(&self, f: F);
 
+    /// Reports the updates for `key` at times greater or equal to an element of `lower` and not greater or equal
+    /// to an element of `upper`, consolidated and sorted by value and then by time.
+    ///
+    /// Batches are visited from the most recent to the oldest, and the traversal stops at the first batch whose
+    /// upper bound is less or equal to `lower`, as it and all older batches can only hold earlier times. Batches
+    /// whose lower bound is greater or equal to `upper` are skipped without being searched. Only the batches that
+    /// overlap the interval are searched, which for recent times are the few most recent batches. As batches may
+    /// have been compacted, times are reported as they are stored in the trace, and may have been advanced by the
+    /// logical compaction frontier.
+    fn updates_through(&mut self, key: &Self::Key, lower: AntichainRef<Self::Time>, upper: AntichainRef<Self::Time>) -> Vec<(Self::Val, Self::Time, Self::R)>
+    where
+        Self::Key: PartialEq,
+        Self::Val: Ord+Clone,
+        Self::Time: PartialOrder+Ord+Clone,
+        Self::R: Semigroup,
+    {
+        let mut batches = Vec::new();
+        self.map_batches(|batch| batches.push(batch.clone()));
+
+        let mut result = Vec::new();
+        for batch in batches.iter().rev() {
+            if PartialOrder::less_equal(&batch.upper().borrow(), &lower) {
+                break;
+            }
+            if PartialOrder::less_equal(&upper, &batch.lower().borrow()) {
+                continue;
+            }
+            let mut cursor = batch.cursor();
+            cursor.seek_key(batch, key);
+            if cursor.get_key(batch) == Some(key) {
+                while let Some(val) = cursor.get_val(batch) {
+                    cursor.map_times(batch, |time, diff| {
+                        if lower.less_equal(time) && !upper.less_equal(time) {
+                            result.push((val.clone(), time.clone(), diff.clone()));
+                        }
+                    });
+                    cursor.step_val(batch);
+                }
+            }
+        }
+        ::consolidation::consolidate_updates(&mut result);
+        result
+    }
+
+    /// Reports the accumulated contents of the trace at `time`.
+    ///
+    /// The result contains each `(key, val)` whose updates at times less or equal to `time` accumulate
+    /// to a non-zero difference, in sorted order. The trace can only report accurate accumulations for
+    /// times beyond its logical compaction frontier, and `None` is returned for other times. The result
+    /// is also only complete if the trace is complete at `time`, as reported by `is_complete`.
+    fn contents_at(&mut self, time: &Self::Time) -> Option<Vec<((Self::Key, Self::Val), Self::R)>>
+    where
+        Self::Key: Clone,
+        Self::Val: Clone,
+        Self::Time: PartialOrder,
+        Self::R: Semigroup,
+    {
+        if !self.get_logical_compaction().less_equal(time) {
+            return None;
+        }
+        let (mut cursor, storage) = self.cursor();
+        let mut result = Vec::new();
+        let mut vals = Vec::new();
+        while let Some(key) = cursor.get_key(&storage) {
+            cursor.accumulate_vals(&storage, time, &mut vals);
+            result.extend(vals.drain(..).map(|(val, diff)| ((key.clone(), val.clone()), diff)));
+            cursor.step_key(&storage);
+        }
+        Some(result)
+    }
+
+    /// Reports summary statistics about the contents of the trace.
+    ///
+    /// The statistics are computed when called, by summing the statistics that each batch reports.
+    fn statistics(&mut self) -> Statistics {
+        let mut stats = Statistics::default();
+        self.map_batches(|batch| {
+            let batch = batch.statistics();
+            stats.batches += batch.batches;
+            stats.keys += batch.keys;
+            stats.vals += batch.vals;
+            stats.updates += batch.updates;
+            if stats.max_key_updates < batch.max_key_updates {
+                stats.max_key_updates = batch.max_key_updates;
+            }
+        });
+        stats
+    }
+
     /// Reads the upper frontier of committed times.
     ///
     ///
@@ -171,6 +315,20 @@ pub trait TraceReader {
         });
     }
 
+    /// Returns true if the trace contains all updates at `time`.
+    ///
+    /// This is the case when `time` is not greater or equal to any element of the upper frontier of
+    /// committed times, and it indicates that cursors will observe the final accumulation at `time`.
+    /// As the trace is shared, this can be used outside of a dataflow to test whether a time is visible.
+    fn is_complete(&mut self, time: &Self::Time) -> bool
+    where
+        Self::Time: Timestamp,
+    {
+        let mut upper = Antichain::new();
+        self.read_upper(&mut upper);
+        !upper.less_equal(time)
+    }
+
     /// Advances `upper` by any empty batches.
     ///
     /// An empty batch whose `batch.lower` bound equals the current
@@ -225,6 +383,23 @@ where <Self as TraceReader>::Batch: Batch {
     /// This method should be logically equivalent to introducing an empty batch whose lower frontier equals
     /// the upper frontier of the most recently introduced batch, and whose upper frontier is empty.
     fn close(&mut self);
+
+    /// Releases any excess capacity held by the trace.
+    ///
+    /// This is intended for use after a bulk load, when the trace is not expected to grow soon.
+    /// The default implementation does nothing.
+    fn shrink_to_fit(&mut self) { }
+
+    /// Compacts the trace's representation to its logical compaction frontier immediately.
+    ///
+    /// Traces may otherwise compact their updates only as they merge batches, leaving updates that would
+    /// cancel once advanced spread across batches. The default implementation does nothing.
+    fn purge(&mut self) { }
+
+    /// Reports the heap allocations of the trace's batches, as `(length, capacity)` pairs in bytes.
+    ///
+    /// The default implementation reports nothing.
+    fn heap_size<F: FnMut(usize, usize)>(&self, _callback: F) { }
 }
 
 /// A batch of updates whose contents may be read.
@@ -261,6 +436,11 @@ where
     fn lower(&self) -> &Antichain<Self::Time> { self.description().lower() }
     /// All times in the batch are not greater or equal to any element of `upper`.
     fn upper(&self) -> &Antichain<Self::Time> { self.description().upper() }
+    /// Summary statistics about the updates of the batch.
+    ///
+    /// The default implementation reads the entire batch. Implementations may instead derive the
+    /// statistics from the lengths of their internal structures.
+    fn statistics(&self) -> Statistics { Statistics::from_batch(self) }
 }
 
 /// An immutable collection of updates.
@@ -284,6 +464,15 @@ pub trait Batch : BatchReader where Self: ::std::marker::Sized {
     fn empty(lower: Antichain<Self::Time>, upper: Antichain<Self::Time>, since: Antichain<Self::Time>) -> Self {
         <Self::Builder>::new().done(lower, upper, since)
     }
+    /// Releases any excess capacity held by the batch.
+    ///
+    /// Batches are often assembled with generous allocations, and this method allows them to be
+    /// trimmed once they are known to be long-lived. The default implementation does nothing.
+    fn shrink_to_fit(&mut self) { }
+    /// Reports the heap allocations of the batch, as `(length, capacity)` pairs in bytes.
+    ///
+    /// The default implementation reports nothing.
+    fn heap_size<F: FnMut(usize, usize)>(&self, _callback: F) { }
 }
 
 /// Functionality for collecting and batching updates.
@@ -340,7 +529,7 @@ pub mod rc_blanket_impls {
     use timely::communication::message::RefOrMut;
 
     use timely::progress::{Antichain, frontier::AntichainRef};
-    use super::{Batch, BatchReader, Batcher, Builder, Merger, Cursor, Description};
+    use super::{Batch, BatchReader, Batcher, Builder, Merger, Cursor, Description, Statistics};
 
     impl<B: BatchReader> BatchReader for Rc<B> {
         type Key = B::Key;
@@ -359,6 +548,8 @@ pub mod rc_blanket_impls {
         fn len(&self) -> usize { (&**self).len() }
         /// Describes the times of the updates in the batch.
         fn description(&self) -> &Description<Self::Time> { (&**self).description() }
+        /// Summary statistics about the updates of the batch.
+        fn statistics(&self) -> Statistics { (&**self).statistics() }
     }
 
     /// Wrapper to provide cursor to nested scope.
@@ -409,6 +600,15 @@ pub mod rc_blanket_impls {
         type Batcher = RcBatcher<B>;
         type Builder = RcBuilder<B>;
         type Merger = RcMerger<B>;
+        /// Trims the batch only if this is the sole reference to it.
+        fn shrink_to_fit(&mut self) {
+            if let Some(batch) = Rc::get_mut(self) {
+                batch.shrink_to_fit();
+            }
+        }
+        fn heap_size<F: FnMut(usize, usize)>(&self, callback: F) {
+            (**self).heap_size(callback);
+        }
     }
 
     /// Wrapper type for batching reference counted batches.
@@ -455,7 +655,7 @@ pub mod abomonated_blanket_impls {
     use timely::communication::message::RefOrMut;
     use timely::progress::{Antichain, frontier::AntichainRef};
 
-    use super::{Batch, BatchReader, Batcher, Builder, Merger, Cursor, Description};
+    use super::{Batch, BatchReader, Batcher, Builder, Merger, Cursor, Description, Statistics};
 
     impl<B: BatchReader+Abomonation> BatchReader for Abomonated<B, Vec<u8>> {
 
@@ -475,6 +675,8 @@ pub mod abomonated_blanket_impls {
         fn len(&self) -> usize { (&**self).len() }
         /// Describes the times of the updates in the batch.
         fn description(&self) -> &Description<Self::Time> { (&**self).description() }
+        /// Summary statistics about the updates of the batch.
+        fn statistics(&self) -> Statistics { (&**self).statistics() }
     }
 
     /// Wrapper to provide cursor to nested scope.
@@ -20,6 +20,23 @@ use lattice::Lattice;
 use trace::TraceReader;
 use trace::cursor::Cursor;
 
+/// Policies for how a shared trace compacts in response to the frontiers of its referees.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompactionPolicy {
+    /// The trace is not logically compacted, and retains its full history.
+    Retain,
+    /// The trace is compacted to the frontiers of its referees as its batches are merged.
+    Lazy,
+    /// As `Lazy`, but the trace is also purged as each batch is inserted, so that its representation
+    /// tracks the frontiers of its referees. Each purge takes time proportional to the size of the trace,
+    /// but batches that bring no updates while the logical frontier is unchanged do not purge it again.
+    Eager,
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> Self { CompactionPolicy::Lazy }
+}
+
 /// A wrapper around a trace which tracks the frontiers of all referees.
 ///
 /// This is an internal type, unlikely to be useful to higher-level programs, but exposed just in case.
@@ -36,6 +53,8 @@ where
     pub physical_compaction: MutableAntichain<Tr::Time>,
     /// The wrapped trace.
     pub trace: Tr,
+    /// The policy for compacting the wrapped trace.
+    pub policy: CompactionPolicy,
 }
 
 impl<Tr> TraceBox<Tr>
@@ -58,13 +77,25 @@ where
             logical_compaction,
             physical_compaction,
             trace: trace,
+            policy: CompactionPolicy::default(),
+        }
+    }
+    /// Changes the policy for compacting the wrapped trace.
+    ///
+    /// The policy only affects future compaction; history already compacted away is not restored.
+    pub fn set_policy(&mut self, policy: CompactionPolicy) {
+        self.policy = policy;
+        if self.policy != CompactionPolicy::Retain {
+            self.trace.set_logical_compaction(self.logical_compaction.frontier());
         }
     }
     /// Replaces elements of `lower` with those of `upper`.
     pub fn adjust_logical_compaction(&mut self, lower: AntichainRef<Tr::Time>, upper: AntichainRef<Tr::Time>) {
         self.logical_compaction.update_iter(upper.iter().cloned().map(|t| (t,1)));
         self.logical_compaction.update_iter(lower.iter().cloned().map(|t| (t,-1)));
-        self.trace.set_logical_compaction(self.logical_compaction.frontier());
+        if self.policy != CompactionPolicy::Retain {
+            self.trace.set_logical_compaction(self.logical_compaction.frontier());
+        }
     }
     /// Replaces elements of `lower` with those of `upper`.
     pub fn adjust_physical_compaction(&mut self, lower: AntichainRef<Tr::Time>, upper: AntichainRef<Tr::Time>) {
@@ -0,0 +1,22 @@
+extern crate timely;
+extern crate differential_dataflow;
+
+use differential_dataflow::input::InputSession;
+
+#[test]
+#[should_panic(expected = "collections differ; updates of `self` minus `other`:\n    (3, 1, 1)\n    (4, 2, -1)")]
+fn assert_eq_reports_updates() {
+    timely::execute_directly(|worker| {
+        let mut input = InputSession::<u64, u64, isize>::new();
+        worker.dataflow(|scope| {
+            let data = input.to_collection(scope);
+            data.filter(|x| *x != 4)
+                .assert_eq(&data.filter(|x| *x != 3));
+        });
+        // The differences, at distinct times, are arranged and reported together.
+        input.update_at(1, 0, 1);
+        input.update_at(3, 1, 1);
+        input.update_at(4, 2, 1);
+        input.advance_to(3);
+    });
+}
@@ -0,0 +1,67 @@
+extern crate timely;
+extern crate differential_dataflow;
+
+use std::sync::mpsc::Receiver;
+
+use timely::dataflow::operators::Capture;
+use timely::dataflow::operators::capture::Event;
+use timely::dataflow::scopes::Child;
+use timely::worker::Worker;
+use timely::communication::allocator::thread::Thread;
+
+use differential_dataflow::Collection;
+use differential_dataflow::input::InputSession;
+
+type Scope<'a> = Child<'a, Worker<Thread>, u64>;
+type Messages = Vec<(u64, Vec<(u64, u64, isize)>)>;
+
+// Introduces updates out of order of both time and record, some of which cancel, and returns the
+// messages the operator produces, in the order it produced them.
+fn messages<F>(logic: F) -> Messages
+where
+    F: for<'a> Fn(&Collection<Scope<'a>, u64>) -> Collection<Scope<'a>, u64>+Send+Sync+'static,
+{
+    let captured: Receiver<Event<u64, (u64, u64, isize)>> = timely::execute_directly(move |worker| {
+        let mut input = InputSession::new();
+        let captured = worker.dataflow(|scope| logic(&input.to_collection(scope)).inner.capture());
+        input.update_at(3, 2, 1);
+        input.update_at(2, 1, 1);
+        input.update_at(5, 0, 1);
+        input.update_at(1, 1, 1);
+        input.update_at(4, 0, 1);
+        input.update_at(2, 1, 1);
+        input.update_at(6, 2, 1);
+        input.update_at(6, 2, -1);
+        input.advance_to(3);
+        input.flush();
+        captured
+    });
+
+    captured
+        .iter()
+        .filter_map(|event| match event {
+            Event::Messages(time, data) => Some((time, data)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn consolidate_sorted_order() {
+    let expected = vec![
+        (0, vec![(4, 0, 1), (5, 0, 1)]),
+        (1, vec![(1, 1, 1), (2, 1, 2)]),
+        (2, vec![(3, 2, 1)]),
+    ];
+    assert_eq!(messages(|collection| collection.consolidate_sorted()), expected);
+}
+
+#[test]
+fn consolidate_complete_order() {
+    let expected = vec![
+        (0, vec![(4, 0, 1), (5, 0, 1)]),
+        (1, vec![(1, 1, 1), (2, 1, 2)]),
+        (2, vec![(3, 2, 1)]),
+    ];
+    assert_eq!(messages(|collection| collection.consolidate_complete()), expected);
+}
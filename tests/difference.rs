@@ -0,0 +1,63 @@
+extern crate differential_dataflow;
+
+use differential_dataflow::difference::{Semigroup, Monoid, Abelian, Multiply, Checked, Rational};
+
+#[test]
+fn checked_arithmetic() {
+    let mut sum = Checked(100i8);
+    sum.plus_equals(&Checked(27));
+    assert_eq!(sum, Checked(127));
+    assert_eq!(Checked(-128i8).multiply(&Checked(1)), Checked(-128));
+    assert_eq!(Checked(5i32).negate(), Checked(-5));
+    assert!(Checked::<i64>::zero().is_zero());
+}
+
+#[test]
+#[should_panic(expected = "overflow in difference addition")]
+fn checked_addition_overflow() {
+    let mut sum = Checked(i64::max_value());
+    sum.plus_equals(&Checked(1));
+}
+
+#[test]
+#[should_panic(expected = "overflow in difference multiplication")]
+fn checked_multiplication_overflow() {
+    Checked(u8::max_value()).multiply(&Checked(2));
+}
+
+#[test]
+#[should_panic(expected = "overflow in difference negation")]
+fn checked_negation_overflow() {
+    Checked(i32::min_value()).negate();
+}
+
+#[test]
+fn rational_normalization() {
+    let half = Rational::new(2, -4);
+    assert_eq!((half.numerator(), half.denominator()), (-1, 2));
+    assert_eq!(half, Rational::new(-3, 6));
+
+    let mut sum = Rational::new(1, 6);
+    sum.plus_equals(&Rational::new(1, 3));
+    assert_eq!((sum.numerator(), sum.denominator()), (1, 2));
+    assert_eq!(Rational::new(2, 3).multiply(&Rational::new(3, 4)), Rational::new(1, 2));
+}
+
+#[test]
+fn rational_zero() {
+    let zero = Rational::new(0, -7);
+    assert!(zero.is_zero());
+    assert_eq!((zero.numerator(), zero.denominator()), (0, 1));
+    assert_eq!(zero, Rational::zero());
+
+    let mut sum = Rational::new(1, 3);
+    sum.plus_equals(&Rational::new(1, 3).negate());
+    assert!(sum.is_zero());
+    assert_eq!(sum, Rational::zero());
+}
+
+#[test]
+#[should_panic(expected = "rational with zero denominator")]
+fn rational_zero_denominator() {
+    Rational::new(1, 0);
+}
@@ -0,0 +1,48 @@
+extern crate timely;
+extern crate differential_dataflow;
+
+use std::rc::Rc;
+use std::cell::RefCell;
+
+use timely::dataflow::operators::probe::Handle;
+
+use differential_dataflow::input::InputSession;
+use differential_dataflow::algorithms::heavy_hitters::HeavyHitters;
+
+#[test]
+fn heavy_hitters_retraction() {
+    timely::execute_directly(|worker| {
+
+        let mut input = InputSession::<usize, (u64, char), isize>::new();
+        let mut probe = Handle::new();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen2 = seen.clone();
+
+        worker.dataflow(|scope| {
+            input.to_collection(scope)
+                 .heavy_hitters(1, 2)
+                 .inspect(move |x| seen2.borrow_mut().push(x.clone()))
+                 .probe_with(&mut probe);
+        });
+
+        input.update((0, 'a'), 3);
+        input.update((0, 'b'), 1);
+        input.advance_to(1);
+        input.flush();
+        worker.step_while(|| probe.less_than(input.time()));
+
+        // Retracting every `a` leaves `b` as the most frequent value.
+        input.update((0, 'a'), -3);
+        input.advance_to(2);
+        input.flush();
+        worker.step_while(|| probe.less_than(input.time()));
+
+        let mut seen = seen.borrow().clone();
+        seen.sort();
+        assert_eq!(seen, vec![
+            ((0, ('a', 3)), 0, 1),
+            ((0, ('a', 3)), 1, -1),
+            ((0, ('b', 1)), 1, 1),
+        ]);
+    });
+}
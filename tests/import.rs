@@ -6,9 +6,14 @@ use timely::dataflow::operators::*;
 use timely::dataflow::operators::capture::Extract;
 use timely::progress::frontier::AntichainRef;
 
+use timely::dataflow::ProbeHandle;
+use timely::worker::Worker;
+use timely::communication::allocator::thread::Thread;
+
 use differential_dataflow::input::InputSession;
 use differential_dataflow::collection::AsCollection;
-use differential_dataflow::operators::arrange::{ArrangeByKey, ArrangeBySelf};
+use differential_dataflow::operators::arrange::{ArrangeByKey, ArrangeBySelf, Catalog, TraceAgent};
+use differential_dataflow::trace::implementations::ord::{OrdValSpine, OrdKeySpine};
 use differential_dataflow::operators::reduce::Reduce;
 use differential_dataflow::trace::TraceReader;
 use itertools::Itertools;
@@ -98,6 +103,64 @@ fn test_import_vanilla() {
     ]);
 }
 
+#[test]
+fn test_import_catalog() {
+    run_test(|input_epochs| {
+        timely::execute(timely::Config::process(4), move |worker| {
+            let ref input_epochs = input_epochs;
+            let index = worker.index();
+            let peers = worker.peers();
+
+            let mut catalog = Catalog::new();
+            let mut input = worker.dataflow(|scope| {
+                let (input, edges) = scope.new_input();
+                let arranged = edges.as_collection()
+                                    .arrange_by_key();
+                catalog.publish("edges", arranged.trace.clone());
+                input
+            });
+            assert!(catalog.get::<OrdValSpine<u64, u64, usize, isize>>("edges").is_none());
+            let (captured,) = worker.dataflow(|scope| {
+                let imported = catalog.import::<_, OrdValSpine<u64, u64, usize, i64>>(scope, "edges").expect("trace not published");
+                assert!(catalog.remove("edges"));
+                let captured =
+                imported
+                    .reduce(|_k, s, t| t.push((s.iter().map(|&(_, w)| w).sum(), 1i64)))
+                    .inner
+                    .exchange(|_| 0)
+                    .capture();
+                (captured,)
+            });
+
+            for (t, changes) in input_epochs.into_iter().enumerate() {
+                if &t != input.time() {
+                    input.advance_to(t);
+                }
+                let &time = input.time();
+                for &((src, dst), w) in changes.into_iter().filter(|&&((src, _), _)| (src as usize) % peers == index) {
+                    input.send(((src, dst), time, w));
+                }
+            }
+            input.close();
+
+            captured
+        }).unwrap().join().into_iter().map(|x| x.unwrap()).next().unwrap()
+    }, vec![
+        (0, vec![
+             ((1, 2), 1), ((2, 1), 1), ((4, 1), 1)]),
+        (1, vec![
+             ((1, 1), 1), ((1, 2), -1), ((2, 1), -1)]),
+        (2, vec![
+             ((1, 1), -1), ((2, 1), 1)]),
+        (3, vec![
+             ((1, 2), 1), ((2, 1), -1), ((4, 1), -1)]),
+        (4, vec![
+             ((1, 1), 1), ((1, 2), -1), ((2, 1), 1), ((4, 1), 1)]),
+        (5, vec![
+             ((1, 1), -1), ((1, 2), 1), ((4, 1), -1)]),
+    ]);
+}
+
 #[test]
 fn test_import_completed_dataflow() {
     // Runs the first dataflow to completion before constructing the subscriber.
@@ -247,3 +310,248 @@ fn import_skewed() {
         (4, vec![((0, 1), 1)]),
     ]);
 }
+
+// Arranges a new input by itself, returning the input, the trace of the arrangement, and a probe of its stream.
+fn arrange_input(worker: &mut Worker<Thread>) -> (InputSession<usize, u64, isize>, TraceAgent<OrdKeySpine<u64, usize, isize>>, ProbeHandle<usize>) {
+    let mut input = InputSession::new();
+    let (trace, probe) = worker.dataflow(|scope| {
+        let arranged = input.to_collection(scope).arrange_by_self();
+        (arranged.trace.clone(), arranged.stream.probe())
+    });
+    (input, trace, probe)
+}
+
+// Advances the input to `time`, and steps the worker until the arrangement has caught up.
+fn advance(worker: &mut Worker<Thread>, input: &mut InputSession<usize, u64, isize>, probe: &ProbeHandle<usize>, time: usize) {
+    input.advance_to(time);
+    input.flush();
+    worker.step_while(|| probe.less_than(input.time()));
+}
+
+#[test]
+fn test_subscribe() {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    timely::execute_directly(|worker| {
+        let (mut input, mut trace, probe) = arrange_input(worker);
+
+        input.insert(1);
+        input.advance_to(1);
+        input.insert(2);
+        input.advance_to(2);
+        input.remove(1);
+        advance(worker, &mut input, &probe, 3);
+
+        // The snapshot is taken at the trace's upper frontier, `3`.
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen2 = seen.clone();
+        let probe2 = worker.dataflow(|scope| {
+            let (subscription, _button) = trace.subscribe(scope, "Subscribe");
+            subscription
+                .inspect(move |x| seen2.borrow_mut().push(x.clone()))
+                .probe()
+        });
+        ::std::mem::drop(trace);
+
+        input.advance_to(4);
+        input.insert(3);
+        advance(worker, &mut input, &probe2, 5);
+
+        let mut seen = seen.borrow().clone();
+        seen.sort();
+        assert_eq!(seen, vec![((2, ()), 3, 1), ((3, ()), 4, 1)]);
+    });
+}
+
+#[test]
+fn test_import_fork() {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    timely::execute_directly(|worker| {
+        let (mut input, trace, probe) = arrange_input(worker);
+
+        input.insert(1);
+        input.insert(2);
+        advance(worker, &mut input, &probe, 1);
+
+        let (mut fork, writer) = trace.fork();
+
+        // Updates to the original trace after the fork are not visible in the fork.
+        input.insert(3);
+        advance(worker, &mut input, &probe, 2);
+
+        // Dropping the writer seals the fork, so that its import completes.
+        ::std::mem::drop(writer);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen2 = seen.clone();
+        let probe2 = worker.dataflow(|scope| {
+            fork.import(scope)
+                .as_collection(|k, _| *k)
+                .inspect(move |x| seen2.borrow_mut().push(x.clone()))
+                .probe()
+        });
+        ::std::mem::drop(fork);
+
+        worker.step_while(|| !probe2.done());
+
+        let mut seen = seen.borrow().clone();
+        seen.sort();
+        assert_eq!(seen, vec![(1, 0, 1), (2, 0, 1)]);
+    });
+}
+
+#[test]
+fn test_purge() {
+    use differential_dataflow::trace::{BatchReader, Cursor};
+
+    timely::execute_directly(|worker| {
+        let (mut input, mut trace, probe) = arrange_input(worker);
+
+        input.insert(1);
+        input.advance_to(1);
+        input.remove(1);
+        input.insert(2);
+        advance(worker, &mut input, &probe, 2);
+
+        trace.set_logical_compaction(AntichainRef::new(&[2]));
+        trace.set_physical_compaction(AntichainRef::new(&[2]));
+        trace.purge();
+
+        let mut total = 0;
+        trace.map_batches(|batch| total += batch.len());
+        assert_eq!(total, 1);
+
+        let (mut cursor, storage) = trace.cursor();
+        assert_eq!(cursor.to_vec(&storage), vec![((2, ()), vec![(2, 1)])]);
+    });
+}
+
+#[test]
+fn test_as_of() {
+    use std::rc::Rc;
+    use std::cell::RefCell;
+
+    timely::execute_directly(|worker| {
+        let (mut input, mut trace, probe) = arrange_input(worker);
+
+        input.insert(1);
+        input.advance_to(1);
+        input.insert(2);
+        input.advance_to(2);
+        input.remove(1);
+        advance(worker, &mut input, &probe, 3);
+
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let seen2 = seen.clone();
+        let probe2 = worker.dataflow(|scope| {
+            let (collection, _button) = trace.as_of(scope, "AsOf", 1).expect("time not beyond compaction");
+            collection
+                .inspect(move |x| seen2.borrow_mut().push(x.clone()))
+                .probe()
+        });
+
+        worker.step_while(|| probe2.less_than(&2));
+
+        let mut seen = seen.borrow().clone();
+        seen.sort();
+        assert_eq!(seen, vec![((1, ()), 1, 1), ((2, ()), 1, 1)]);
+    });
+}
+
+#[test]
+fn test_compaction_policies() {
+    use differential_dataflow::operators::arrange::CompactionPolicy;
+    use differential_dataflow::trace::{BatchReader, Cursor};
+
+    // Returns the number of non-empty batches and the contents of the trace, after its frontiers
+    // have advanced to `3` and a further batch has been inserted.
+    fn run(policy: CompactionPolicy) -> (usize, Vec<((u64, ()), Vec<(usize, isize)>)>) {
+        timely::execute_directly(move |worker| {
+            let (mut input, mut trace, probe) = arrange_input(worker);
+            trace.set_compaction_policy(policy);
+
+            input.insert(1);
+            input.advance_to(1);
+            input.remove(1);
+            input.insert(2);
+            input.advance_to(2);
+            input.insert(3);
+            advance(worker, &mut input, &probe, 3);
+
+            trace.set_logical_compaction(AntichainRef::new(&[3]));
+            trace.set_physical_compaction(AntichainRef::new(&[3]));
+
+            advance(worker, &mut input, &probe, 4);
+
+            let mut batches = 0;
+            trace.map_batches(|batch| if !batch.is_empty() { batches += 1; });
+            let (mut cursor, storage) = trace.cursor();
+            (batches, cursor.to_vec(&storage))
+        })
+    }
+
+    // Retained history is not compacted.
+    let (_, contents) = run(CompactionPolicy::Retain);
+    assert_eq!(contents, vec![
+        ((1, ()), vec![(0, 1), (1, -1)]),
+        ((2, ()), vec![(1, 1)]),
+        ((3, ()), vec![(2, 1)]),
+    ]);
+
+    // Lazily compacted history accumulates correctly, whether or not merges have compacted it.
+    let (batches, contents) = run(CompactionPolicy::Lazy);
+    assert!(batches >= 1);
+    let mut accumulated = contents
+        .into_iter()
+        .map(|(key, updates)| (key, updates.into_iter().map(|(_, diff)| diff).sum::<isize>()))
+        .filter(|&(_, diff)| diff != 0)
+        .collect::<Vec<_>>();
+    accumulated.sort();
+    assert_eq!(accumulated, vec![((2, ()), 1), ((3, ()), 1)]);
+
+    // Eagerly compacted history is a single batch advanced to the frontier.
+    let (batches, contents) = run(CompactionPolicy::Eager);
+    assert_eq!(batches, 1);
+    assert_eq!(contents, vec![
+        ((2, ()), vec![(3, 1)]),
+        ((3, ()), vec![(3, 1)]),
+    ]);
+}
+
+#[test]
+fn test_eager_compaction_idle() {
+    use std::rc::Rc;
+    use differential_dataflow::operators::arrange::CompactionPolicy;
+    use differential_dataflow::trace::BatchReader;
+
+    timely::execute_directly(move |worker| {
+        let (mut input, mut trace, probe) = arrange_input(worker);
+        trace.set_compaction_policy(CompactionPolicy::Eager);
+
+        input.insert(1);
+        input.insert(2);
+        advance(worker, &mut input, &probe, 1);
+
+        let mut purged = Vec::new();
+        trace.map_batches(|batch| if !batch.is_empty() { purged.push(batch.clone()); });
+        assert_eq!(purged.len(), 1);
+
+        // Without new updates or compaction, the purged batch is left in place.
+        advance(worker, &mut input, &probe, 2);
+        let mut batches = Vec::new();
+        trace.map_batches(|batch| if !batch.is_empty() { batches.push(batch.clone()); });
+        assert_eq!(batches.len(), 1);
+        assert!(Rc::ptr_eq(&batches[0], &purged[0]));
+
+        // Advancing the logical frontier purges the trace again.
+        trace.set_logical_compaction(AntichainRef::new(&[2]));
+        advance(worker, &mut input, &probe, 3);
+        let mut batches = Vec::new();
+        trace.map_batches(|batch| if !batch.is_empty() { batches.push(batch.clone()); });
+        assert_eq!(batches.len(), 1);
+        assert!(!Rc::ptr_eq(&batches[0], &purged[0]));
+    });
+}
@@ -101,4 +101,48 @@ fn join_scaling(scale: u64) {
 
     let extracted = data.extract();
     assert_eq!(extracted.len(), 0);
-}
\ No newline at end of file
+}
+#[test]
+fn join_fuel() {
+
+    use differential_dataflow::input::InputSession;
+
+    // Joins the records `0 .. 100` with themselves on ten keys, returning the output and the number
+    // of steps the worker took to produce it.
+    fn run(fuel: Option<usize>) -> (Vec<((u64, (u64, u64)), u64, isize)>, usize) {
+        let mut config = timely::Config::thread();
+        differential_dataflow::configure(&mut config.worker, &differential_dataflow::Config::default().join_fuel(fuel));
+        let guards = timely::execute(config, |worker| {
+            let mut input = InputSession::<u64, u64, isize>::new();
+            let (probe, captured) = worker.dataflow(|scope| {
+                let pairs = input.to_collection(scope).map(|x| (x % 10, x));
+                let joined = pairs.join(&pairs);
+                (joined.probe(), joined.inner.capture())
+            });
+            for x in 0 .. 100 {
+                input.insert(x);
+            }
+            input.advance_to(1);
+            input.flush();
+            let mut steps = 0;
+            while probe.less_than(input.time()) {
+                worker.step();
+                steps += 1;
+            }
+            (captured, steps)
+        }).unwrap();
+
+        let (captured, steps) = guards.join().pop().unwrap().unwrap();
+        let mut output = captured.extract().into_iter().flat_map(|(_, data)| data).collect::<Vec<_>>();
+        output.sort();
+        (output, steps)
+    }
+
+    let (output, steps) = run(None);
+    let (fueled_output, fueled_steps) = run(Some(10));
+
+    // A join with little fuel produces the same output, but yields to the worker along the way.
+    assert_eq!(output.len(), 1000);
+    assert_eq!(fueled_output, output);
+    assert!(fueled_steps > steps);
+}
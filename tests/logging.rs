@@ -0,0 +1,105 @@
+extern crate timely;
+extern crate serde_json;
+extern crate differential_dataflow;
+
+use timely::dataflow::operators::Probe;
+
+use differential_dataflow::input::InputSession;
+use differential_dataflow::operators::arrange::ArrangeByKey;
+use differential_dataflow::logging;
+
+#[test]
+fn plan_json() {
+    timely::execute_directly(|worker| {
+
+        let plan = logging::enable_plan(worker);
+
+        let mut input = InputSession::new();
+        let _trace = worker.dataflow::<u64,_,_>(|scope| {
+            input.to_collection(scope)
+                 .arrange_by_key_named("Arrange \"quoted\"")
+                 .trace
+        });
+
+        input.insert((0u64, 1u64));
+        input.advance_to(1);
+        input.flush();
+        worker.step();
+
+        let json: serde_json::Value = serde_json::from_str(&plan.to_json()).expect("invalid JSON");
+        let operators = json["operators"].as_array().expect("operators absent");
+        let arrange = operators
+            .iter()
+            .find(|op| op["name"] == "Arrange \"quoted\"")
+            .expect("arrangement absent");
+
+        assert!(arrange["key"] == "u64");
+        assert!(arrange["val"] == "u64");
+        assert!(arrange["shares"] == 1);
+    });
+}
+
+#[test]
+fn metrics_arrangement() {
+    timely::execute_directly(|worker| {
+
+        let registry = logging::enable_metrics(worker);
+
+        let mut input = InputSession::new();
+        let (probe, _trace) = worker.dataflow::<u64,_,_>(|scope| {
+            let arranged = input.to_collection(scope).arrange_by_key();
+            (arranged.stream.probe(), arranged.trace)
+        });
+
+        input.insert((0u64, 1u64));
+        input.insert((1u64, 2u64));
+        input.insert((2u64, 3u64));
+        input.advance_to(1);
+        input.flush();
+        worker.step_while(|| probe.less_than(input.time()));
+
+        let (_, metrics) = registry
+            .snapshot()
+            .into_iter()
+            .find(|(_, metrics)| metrics.batches > 0)
+            .expect("arrangement metrics absent");
+
+        assert_eq!(metrics.records, 3);
+        assert_eq!(metrics.size, 3);
+        assert_eq!(metrics.records_in, 3);
+        assert_eq!(registry.size(), 3);
+    });
+}
+
+#[test]
+fn flush_within_budget() {
+    timely::execute_directly(|worker| {
+
+        // The plan is enabled after the metrics, and must not disable them.
+        let registry = logging::enable_metrics(worker);
+        let _plan = logging::enable_plan(worker);
+
+        let mut input = InputSession::new();
+        let (probe, _trace) = worker.dataflow::<u64,_,_>(|scope| {
+            let arranged = input.to_collection(scope).arrange_by_key();
+            (arranged.stream.probe(), arranged.trace)
+        });
+
+        // Within budget, the session is flushed without stepping the worker.
+        input.insert((0u64, 1u64));
+        input.insert((1u64, 2u64));
+        input.insert((2u64, 3u64));
+        input.advance_to(1);
+        input.flush_within(worker, &probe, &registry, 10);
+        assert!(probe.less_than(&1));
+        worker.step_while(|| probe.less_than(input.time()));
+        assert_eq!(registry.size(), 3);
+
+        // Over budget, the worker is stepped until the arrangement has caught up.
+        input.insert((3u64, 4u64));
+        input.advance_to(2);
+        input.flush_within(worker, &probe, &registry, 2);
+        assert!(!probe.less_than(&2));
+        assert_eq!(registry.size(), 4);
+    });
+}
@@ -0,0 +1,98 @@
+extern crate timely;
+extern crate differential_dataflow;
+
+use timely::order::Product;
+
+use differential_dataflow::operators::{Join, Reduce, Threshold, Count};
+use differential_dataflow::reference::{compare, Generator};
+
+#[test]
+fn reference_map_filter() {
+    for seed in 0 .. 10 {
+        compare(
+            Generator::new(seed).updates(100, 20, 10),
+            |input| input.map(|x| x / 2).filter(|x| x % 3 != 0),
+            |input| input.map(|x| x / 2).filter(|x| x % 3 != 0),
+        );
+    }
+}
+
+#[test]
+fn reference_unordered_updates() {
+    for seed in 0 .. 10 {
+        let mut updates = Generator::new(seed).updates(100, 20, 10);
+        updates.reverse();
+        compare(
+            updates,
+            |input| input.map(|x| (x % 4, x)).count(),
+            |input| input.map(|x| (x % 4, x)).count(),
+        );
+    }
+}
+
+#[test]
+fn reference_count_distinct() {
+    for seed in 0 .. 10 {
+        compare(
+            Generator::new(seed).updates(100, 20, 10),
+            |input| input.map(|x| (x % 4, x)).distinct().count(),
+            |input| input.map(|x| (x % 4, x)).distinct().count(),
+        );
+    }
+}
+
+#[test]
+fn reference_join() {
+    for seed in 0 .. 10 {
+        compare(
+            Generator::new(seed).updates(100, 20, 10),
+            |input| {
+                let pairs = input.map(|x| (x % 5, x));
+                pairs.join(&pairs).map(|(k, (x, y))| (k, x + y))
+            },
+            |input| {
+                let pairs = input.map(|x| (x % 5, x));
+                pairs.join(&pairs).map(|(k, (x, y))| (k, x + y))
+            },
+        );
+    }
+}
+
+#[test]
+fn reference_reduce() {
+    for seed in 0 .. 10 {
+        compare(
+            Generator::new(seed).updates(100, 20, 10),
+            |input| input.map(|x| (x % 3, x)).reduce(|_key, input, output| output.push((*input[0].0, 1))),
+            |input| input.map(|x| (x % 3, x)).reduce(|_key, input, output| output.push((*input[0].0, 1))),
+        );
+    }
+}
+
+#[test]
+fn reference_partially_ordered_times() {
+    for seed in 0 .. 10 {
+        let updates = Generator::new(seed)
+            .updates(100, 20, 10)
+            .into_iter()
+            .map(|(data, time, diff)| (data, Product::new(time % 3, time / 3), diff as i64))
+            .collect();
+        compare(
+            updates,
+            |input| input.map(|x| (x % 4, x)).distinct().count(),
+            |input| input.map(|x| (x % 4, x)).distinct().count(),
+        );
+    }
+}
+
+#[test]
+fn generator_nonzero_state() {
+    let mut generator = Generator::new(0x9E37_79B9_7F4A_7C15);
+    assert!((0 .. 10).any(|_| generator.next_u64() != 0));
+}
+
+#[test]
+#[should_panic(expected = "records and rounds must be positive")]
+fn generator_zero_rounds() {
+    Generator::new(0).updates(10, 10, 0);
+}
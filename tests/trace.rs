@@ -7,7 +7,7 @@ use timely::dataflow::operators::generic::OperatorInfo;
 use timely::progress::{Antichain, frontier::AntichainRef};
 
 use differential_dataflow::trace::implementations::ord::OrdValBatch;
-use differential_dataflow::trace::{Trace, TraceReader, Batch, Batcher};
+use differential_dataflow::trace::{Trace, TraceReader, Batch, BatchReader, Batcher, Statistics};
 use differential_dataflow::trace::cursor::Cursor;
 use differential_dataflow::trace::implementations::spine_fueled::Spine;
 
@@ -64,3 +64,169 @@ fn test_trace() {
     let vec_4 = cursor4.to_vec(&storage4);
     assert_eq!(vec_4, vec_3);
 }
+
+#[test]
+fn test_is_complete() {
+    let mut trace = get_trace();
+
+    assert!(trace.is_complete(&0));
+    assert!(trace.is_complete(&2));
+    assert!(!trace.is_complete(&3));
+    assert!(!trace.is_complete(&4));
+}
+
+#[test]
+fn test_fork() {
+    let mut trace = get_trace();
+    let mut fork = trace.fork();
+
+    let (mut cursor1, storage1) = trace.cursor();
+    let (mut cursor2, storage2) = fork.cursor();
+    assert_eq!(cursor1.to_vec(&storage1), cursor2.to_vec(&storage2));
+
+    // Updates inserted into the fork are not visible in the original.
+    let mut batcher = <<IntegerTrace as TraceReader>::Batch as Batch>::Batcher::new();
+    use timely::communication::message::RefOrMut;
+    batcher.push_batch(RefOrMut::Mut(&mut vec![((3, 4), 3, 1)]));
+    batcher.seal(Antichain::from_elem(3));
+    fork.insert(batcher.seal(Antichain::from_elem(4)));
+
+    let (mut cursor1, storage1) = trace.cursor();
+    let (mut cursor2, storage2) = fork.cursor();
+    assert_eq!(cursor1.to_vec(&storage1).len(), 2);
+    assert_eq!(cursor2.to_vec(&storage2).len(), 3);
+}
+
+#[test]
+fn test_purge() {
+    let mut trace = get_trace();
+    trace.set_logical_compaction(AntichainRef::new(&[3]));
+    trace.set_physical_compaction(AntichainRef::new(&[3]));
+    trace.purge();
+
+    let (mut cursor, storage) = trace.cursor();
+    assert_eq!(cursor.to_vec(&storage), vec![((1, 2), vec![(3, 1)])]);
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let mut trace = get_trace();
+    let (mut cursor, storage) = trace.cursor();
+    let before = cursor.to_vec(&storage);
+
+    trace.shrink_to_fit();
+
+    let (mut cursor, storage) = trace.cursor();
+    assert_eq!(cursor.to_vec(&storage), before);
+}
+
+#[test]
+fn test_heap_size() {
+    let trace = get_trace();
+    let mut length = 0;
+    let mut capacity = 0;
+    trace.heap_size(|len, cap| { length += len; capacity += cap; });
+    assert!(length > 0);
+    assert!(length <= capacity);
+}
+
+#[test]
+fn test_keys() {
+    let mut trace = get_trace();
+
+    let (mut cursor, storage) = trace.cursor();
+    assert_eq!(cursor.keys(&storage), vec![1, 2]);
+
+    let mut counts = Vec::new();
+    cursor.for_each_key(&storage, |key, cursor, storage| {
+        let mut count = 0;
+        while cursor.val_valid(storage) {
+            cursor.map_times(storage, |_, _| count += 1);
+            cursor.step_val(storage);
+        }
+        counts.push((*key, count));
+    });
+    assert_eq!(counts, vec![(1, 1), (2, 2)]);
+}
+
+#[test]
+fn test_key_stats() {
+    let mut trace = get_trace();
+
+    let (mut cursor, storage) = trace.cursor();
+    let stats = cursor.key_stats(&storage, &2);
+    assert_eq!((stats.vals, stats.times, stats.updates, stats.accumulated), (1, 2, 2, 0));
+    assert_eq!(cursor.key_stats(&storage, &3).updates, 0);
+
+    let heaviest = cursor.heaviest_keys(&storage, 1);
+    assert_eq!(heaviest.len(), 1);
+    assert_eq!(heaviest[0].0, 2);
+}
+
+#[test]
+fn test_updates_between() {
+    let mut trace = get_trace();
+    let (mut cursor, storage) = trace.cursor();
+
+    assert_eq!(cursor.updates_between(&storage, &2, &0, &3), vec![(1, vec![(3, 1)]), (2, vec![(3, -1)])]);
+    assert_eq!(cursor.updates_between(&storage, &2, &2, &3), vec![(2, vec![(3, -1)])]);
+    assert!(cursor.updates_between(&storage, &2, &0, &1).is_empty());
+    assert_eq!(cursor.updates_between(&storage, &1, &0, &1), vec![(0, vec![(2, 1)])]);
+}
+
+#[test]
+fn test_updates_through() {
+    let mut trace = get_trace();
+
+    assert_eq!(trace.updates_through(&2, AntichainRef::new(&[0]), AntichainRef::new(&[2])), vec![(3, 1, 1)]);
+    assert_eq!(trace.updates_through(&2, AntichainRef::new(&[0]), AntichainRef::new(&[3])), vec![(3, 1, 1), (3, 2, -1)]);
+    assert_eq!(trace.updates_through(&2, AntichainRef::new(&[2]), AntichainRef::new(&[3])), vec![(3, 2, -1)]);
+    assert!(trace.updates_through(&1, AntichainRef::new(&[0]), AntichainRef::new(&[0])).is_empty());
+}
+
+#[test]
+fn test_accumulate_vals() {
+    let mut trace = get_trace();
+    let (mut cursor, storage) = trace.cursor();
+
+    let mut target = Vec::new();
+    cursor.seek_key(&storage, &2);
+    cursor.accumulate_vals(&storage, &1, &mut target);
+    assert_eq!(target, vec![(&3, 1)]);
+
+    target.clear();
+    cursor.accumulate_vals(&storage, &2, &mut target);
+    assert!(target.is_empty());
+}
+
+#[test]
+fn test_contents_at() {
+    let mut trace = get_trace();
+
+    assert_eq!(trace.contents_at(&0), Some(vec![((1, 2), 1)]));
+    assert_eq!(trace.contents_at(&1), Some(vec![((1, 2), 1), ((2, 3), 1)]));
+    assert_eq!(trace.contents_at(&2), Some(vec![((1, 2), 1)]));
+
+    trace.set_logical_compaction(AntichainRef::new(&[2]));
+    assert_eq!(trace.contents_at(&1), None);
+}
+
+#[test]
+fn test_statistics() {
+    let mut trace = get_trace();
+
+    // Each batch holds one update, for keys counted once per batch.
+    let stats = trace.statistics();
+    assert_eq!((stats.batches, stats.keys, stats.vals, stats.updates, stats.max_key_updates), (3, 3, 3, 3, 1));
+    assert_eq!(stats.skew(), 1.0);
+
+    // Merging the batches is reflected in the statistics.
+    trace.set_physical_compaction(AntichainRef::new(&[3]));
+    trace.purge();
+    let stats = trace.statistics();
+    assert_eq!((stats.batches, stats.keys, stats.vals, stats.updates, stats.max_key_updates), (1, 2, 2, 3, 2));
+    assert!(stats.skew() > 1.0);
+
+    // Statistics read from the batch layers agree with those read through a cursor.
+    trace.map_batches(|batch| assert_eq!(batch.statistics(), Statistics::from_batch(batch)));
+}